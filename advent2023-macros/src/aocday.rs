@@ -0,0 +1,54 @@
+use crate::makefunc::{expand, MakeFunc};
+
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use syn::{parse2, Error};
+
+// Declare everything about a day in one place: the solution as with make_func!,
+// which registers it for the CLI and benches, and its example tests
+// Example:
+// aoc_day! {
+//     7p2; // or 2023:7p2, the year defaults to 2023
+//     PARSER: 7; // optional, as are the other make_func! options
+//     EXAMPLE: 5905; // or "day7p2" => 5905; or ["day7p2" => 5905, ...]; read from inputs/YYYY/
+//     { function body }
+// }
+pub fn aoc_day(input: TokenStream) -> TokenStream {
+    let span = input.clone();
+    let make_func: MakeFunc = match parse2(input) {
+        Ok(make_func) => make_func,
+        Err(err) => return err.to_compile_error(),
+    };
+    if make_func.examples.is_empty() {
+        return Error::new_spanned(span, "aoc_day! needs an EXAMPLE").to_compile_error();
+    }
+
+    let day = make_func.day;
+    let try_name = format_ident!("try_{}", day.to_string());
+    let mod_name = format_ident!("{}_examples", day.to_string());
+    let tests: TokenStream = make_func.examples.iter().map(|case| {
+        let case_name = format_ident!("{}", case.test_name());
+        let case_file = format!("/inputs/{}/{}_ex.dat", day.year(), case.name);
+        let lit = &case.output;
+        quote::quote! {
+            #[test]
+            fn #case_name() {
+                let input: Vec<&str> = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), #case_file))
+                    .lines()
+                    .collect();
+                let output = super::#try_name(&input).unwrap_or_else(|err| panic!("{}", err));
+                assert_eq!(#lit, output);
+            }
+        }
+    }).collect();
+
+    let funcs = expand(make_func);
+    quote::quote! {
+        #funcs
+
+        #[cfg(test)]
+        mod #mod_name {
+            #tests
+        }
+    }
+}
@@ -1,3 +1,4 @@
+mod aocday;
 mod makebenches;
 mod makefunc;
 mod maketests;
@@ -19,3 +20,8 @@ pub fn make_benches(input: TokenStream) -> TokenStream {
 pub fn make_func(input: TokenStream) -> TokenStream {
     makefunc::make_func(input.into()).into()
 }
+
+#[proc_macro]
+pub fn aoc_day(input: TokenStream) -> TokenStream {
+    aocday::aoc_day(input.into()).into()
+}
@@ -1,37 +1,32 @@
-use crate::util::{
-    parse_block, parse_days, parse_input_path, AccessDays, AccessPath, Day, IdentMap,
-};
+use crate::util::{parse_block, parse_input_path, AccessPath, IdentMap};
 
 use phf::phf_map;
-use proc_macro2::TokenStream;
-use quote::format_ident;
+use proc_macro2::{Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream, Result},
-    parse2,
+    parse2, Error, LitInt,
 };
 
-// Make benchmarks for advent of code 2023
+// Make benchmarks for advent of code 2023, a group for every registered solution
+// with day N parse, day N solve and day N total (day N p2 ... for part two)
+// Days without an input file are benched on a generated input instead
 // Example:
 // make_benches! {
 //   INPUT_PATH: str;
-//   DAYS: [1, 1p2, ...];
+//   GENERATED_SIZE: 1000; // optional, lines to generate
 // }
 
+const DEFAULT_GENERATED_SIZE: usize = 1000;
+
 pub struct MakeBenches {
     input_path: String,
-    days: Vec<Day>,
+    generated_size: usize,
 }
 
 #[derive(Default)]
 struct ParseData {
     path: Option<String>,
-    days: Option<Vec<Day>>,
-}
-
-impl AccessDays for ParseData {
-    fn access_days(&mut self) -> &mut Option<Vec<Day>> {
-        &mut self.days
-    }
+    generated_size: Option<usize>,
 }
 
 impl AccessPath for ParseData {
@@ -40,9 +35,15 @@ impl AccessPath for ParseData {
     }
 }
 
+fn parse_generated_size(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    let size: LitInt = input.parse()?;
+    state.generated_size = Some(size.base10_parse()?);
+    Ok(())
+}
+
 const KEYWORDS: IdentMap<ParseData> = phf_map! {
     "INPUT_PATH" => parse_input_path,
-    "DAYS" => parse_days,
+    "GENERATED_SIZE" => parse_generated_size,
 };
 
 impl Parse for MakeBenches {
@@ -51,57 +52,71 @@ impl Parse for MakeBenches {
 
         if let ParseData {
             path: Some(input_path),
-            days: Some(days),
+            generated_size,
         } = parse_state
         {
-            Ok(Self { input_path, days })
+            Ok(Self {
+                input_path,
+                generated_size: generated_size.unwrap_or(DEFAULT_GENERATED_SIZE),
+            })
         } else {
-            Err(input.error("Missing required fields in make_tests block."))
+            Err(Error::new(
+                Span::call_site(),
+                "make_benches! needs an INPUT_PATH",
+            ))
         }
     }
 }
 
 pub fn make_benches(input: TokenStream) -> TokenStream {
-    let ast: MakeBenches = parse2(input).expect("Failed to parse make_benches AST");
+    let ast: MakeBenches = match parse2(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
+    let input_path = ast.input_path;
+    let generated_size = ast.generated_size;
 
-    let setup = [quote::quote! {
+    quote::quote! {
         use std::fs::File;
         use std::io::{BufRead, BufReader};
-        use criterion::{black_box, criterion_group, criterion_main, Criterion};
-    }];
-    let ast_iter = ast.days.iter().map(|day| {
-        let fn_ident = format_ident!("{}", day.to_string());
-        let day_txt = day.to_bench_name();
-        let input_file = format!("{}/day{}.dat", ast.input_path, day.number);
-        let expect_txt = format!("Could not find file {}", input_file);
+        use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 
-        quote::quote! {
-            fn #fn_ident(c: &mut Criterion) {
-                let input_file =
-                    File::open(#input_file).expect(#expect_txt);
-                let buf_read = BufReader::new(input_file);
-                let lines: Vec<String> = buf_read.lines().flatten().collect();
+        fn solutions(c: &mut Criterion) {
+            advent2023::solutions().into_iter().for_each(|solution| {
+                let input_file = advent2023::inputs::input_path(#input_path, solution.day);
+                let lines: Vec<String> = match File::open(&input_file) {
+                    Ok(input) => BufReader::new(input).lines().flatten().collect(),
+                    Err(_) => {
+                        eprintln!("{} not found, using a generated input", input_file.display());
+                        advent2023::generators::generate(
+                            solution.day,
+                            #generated_size,
+                            advent2023::generators::DEFAULT_SEED,
+                        )
+                        .unwrap_or_else(|| panic!("Could not find file {}", input_file.display()))
+                    }
+                };
                 let str_lines: Vec<&str> = lines.iter().map(String::as_str).collect();
-                c.bench_function(#day_txt, |b| {
-                    b.iter(|| advent2023::#fn_ident(black_box(&str_lines)))
+                let name = solution.day.bench_name();
+                let mut group = c.benchmark_group(&name);
+                group.bench_function(format!("{} parse", name), |b| {
+                    b.iter(|| (solution.parse)(black_box(&str_lines)))
+                });
+                group.bench_function(format!("{} solve", name), |b| {
+                    b.iter_batched(
+                        || (solution.parse)(&str_lines).unwrap_or_else(|err| panic!("{}", err)),
+                        |parsed| parsed.solve(),
+                        BatchSize::LargeInput,
+                    )
+                });
+                group.bench_function(format!("{} total", name), |b| {
+                    b.iter(|| (solution.run)(black_box(&str_lines)))
                 });
-            }
+                group.finish();
+            });
         }
-    });
-
-    let names: Vec<_> = ast
-        .days
-        .iter()
-        .map(|day| format_ident!("{}", day.to_string()))
-        .collect();
-    let ending = [quote::quote! {
-        criterion_group!(
-            benches,
-            #(#names),*
-        );
 
+        criterion_group!(benches, solutions);
         criterion_main! { benches }
-    }];
-
-    setup.into_iter().chain(ast_iter).chain(ending).collect()
+    }
 }
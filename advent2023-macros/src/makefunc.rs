@@ -1,12 +1,16 @@
-use crate::util::Day;
+use crate::{
+    maketests::{ExampleCase, Expected},
+    util::Day,
+};
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::format_ident;
+use std::collections::HashMap;
 use syn::{
     parse::{Parse, ParseStream},
     parse2,
-    token::{Colon, Semi},
-    Block, Ident, Result, Type,
+    token::{Colon, FatArrow, Semi},
+    Block, Error, ExprPath, Ident, LitStr, Result, Type,
 };
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -18,7 +22,7 @@ impl Parse for Parser {
     fn parse(input: ParseStream) -> Result<Self> {
         let id: Ident = input.parse()?;
         if id.to_string().as_str() != "PARSER" {
-            return Err(input.error("Wrong identifier"));
+            return Err(Error::new(id.span(), "Wrong identifier"));
         }
 
         input.parse::<Colon>()?;
@@ -37,7 +41,7 @@ impl Parse for Output {
     fn parse(input: ParseStream) -> Result<Self> {
         let id: Ident = input.parse()?;
         if id.to_string().as_str() != "OUTPUT" {
-            return Err(input.error("Wrong identifier"));
+            return Err(Error::new(id.span(), "Wrong identifier"));
         }
 
         input.parse::<Colon>()?;
@@ -47,6 +51,26 @@ impl Parse for Output {
     }
 }
 
+// EXPLAIN: path; a fn(&[&str]) -> Result<String, AocError> describing how the answer came about
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+struct Explain {
+    explain: ExprPath,
+}
+
+impl Parse for Explain {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        if id.to_string().as_str() != "EXPLAIN" {
+            return Err(Error::new(id.span(), "Wrong identifier"));
+        }
+
+        input.parse::<Colon>()?;
+        let explain: ExprPath = input.parse()?;
+        input.parse::<Semi>()?;
+        Ok(Self { explain })
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 struct NoOkay;
 
@@ -54,7 +78,7 @@ impl Parse for NoOkay {
     fn parse(input: ParseStream) -> Result<Self> {
         let id: Ident = input.parse()?;
         if id.to_string().as_str() != "NO_OKAY" {
-            return Err(input.error("Wrong identifier"));
+            return Err(Error::new(id.span(), "Wrong identifier"));
         }
 
         input.parse::<Semi>()?;
@@ -62,21 +86,145 @@ impl Parse for NoOkay {
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+struct SkipInvalid;
+
+impl Parse for SkipInvalid {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        if id.to_string().as_str() != "SKIP_INVALID" {
+            return Err(Error::new(id.span(), "Wrong identifier"));
+        }
+
+        input.parse::<Semi>()?;
+        Ok(Self)
+    }
+}
+
+// Example inputs and their expected outputs, aoc_day! only
+// EXAMPLE: literal; reads inputs/YYYY/dayN_ex.dat (part one's example)
+// EXAMPLE: "name" => literal; reads inputs/YYYY/name_ex.dat
+// EXAMPLE: ["name" => literal, ...]; one test per case
+#[derive(PartialEq, Eq, Clone, Debug)]
+struct Example {
+    // None for the day's default example
+    cases: Vec<(Option<String>, syn::Lit)>,
+}
+
+impl Parse for Example {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        if id.to_string().as_str() != "EXAMPLE" {
+            return Err(Error::new(id.span(), "Wrong identifier"));
+        }
+
+        input.parse::<Colon>()?;
+        let cases = if input.peek(LitStr) && input.peek2(FatArrow) {
+            let case: ExampleCase = input.parse()?;
+            vec![(Some(case.name), case.output)]
+        } else {
+            match input.parse()? {
+                Expected::Single(lit) => vec![(None, lit)],
+                Expected::Cases(cases) => cases
+                    .into_iter()
+                    .map(|case| (Some(case.name), case.output))
+                    .collect(),
+            }
+        };
+        input.parse::<Semi>()?;
+        Ok(Self { cases })
+    }
+}
+
+// How the input is handed to the parser
+// LINES: parser::dayN::parse_line on every line, input is an iterator of the Ok values
+//        A line it can't parse all of is an error, with SKIP_INVALID those lines are
+//        skipped instead and check_dayN errors on the first, for strict runs
+// WHOLE: parser::dayN::parse_input on all the lines, input is its Ok value
+// SECTIONS: parser::dayN::parse_section on each blank line separated section,
+//           input is an ordered iterator of the Ok values
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+enum InputMode {
+    #[default]
+    Lines,
+    Whole,
+    Sections,
+}
+
+impl Parse for InputMode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        if id.to_string().as_str() != "INPUT" {
+            return Err(Error::new(id.span(), "Wrong identifier"));
+        }
+
+        input.parse::<Colon>()?;
+        let mode: Ident = input.parse()?;
+        let mode = match mode.to_string().as_str() {
+            "LINES" => Self::Lines,
+            "WHOLE" => Self::Whole,
+            "SECTIONS" => Self::Sections,
+            _ => return Err(Error::new(mode.span(), "expected LINES, WHOLE or SECTIONS")),
+        };
+        input.parse::<Semi>()?;
+        Ok(mode)
+    }
+}
+
+// How the parsed input is iterated, LINES and SECTIONS only
+// By default NO_OKAY and SKIP_INVALID lines are streamed to the body as a parallel
+// iterator, which can't zip or enumerate. Lines that can fail are collected to
+// find the first error, and sections are collected, so both are indexed anyway
+// ORDERED: collected into an indexed parallel iterator in input order
+// SEQUENTIAL: a plain iterator in input order
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, Default)]
+enum Order {
+    #[default]
+    Unordered,
+    Ordered,
+    Sequential,
+}
+
+impl Parse for Order {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let id: Ident = input.parse()?;
+        let order = match id.to_string().as_str() {
+            "ORDERED" => Self::Ordered,
+            "SEQUENTIAL" => Self::Sequential,
+            _ => return Err(Error::new(id.span(), "Wrong identifier")),
+        };
+
+        input.parse::<Semi>()?;
+        Ok(order)
+    }
+}
+
 // Make a day solving function for advent of code 2023
 // Example:
 // make_func! {
 //     <DaySpecifier>;
 //     PARSER: <DaySpecifier>; // optional
-//     OUTPUT: i64; // optional
-//     NO_OKAY; // optional
+//     OUTPUT: i64; // optional, anything advent2023::Answer converts from or a Result of one
+//     INPUT: LINES; // optional, LINES, WHOLE or SECTIONS
+//     NO_OKAY; // optional, LINES only, parse_line returns the value itself
+//     SKIP_INVALID; // optional, LINES only
+//     ORDERED; // optional, or SEQUENTIAL
+//     EXPLAIN: explain_day5p2; // optional, for aoc --explain
 //     { function body }
 // }
-#[derive(PartialEq, Eq, Clone, Debug, Hash)]
-struct MakeFunc {
-    day: Day,
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MakeFunc {
+    pub day: Day,
     parser: Day,
     output: Type,
+    input: InputMode,
+    order: Order,
     no_ok: bool,
+    skip_invalid: bool,
+    explain: Option<ExprPath>,
+    pub examples: Vec<ExampleCase>,
+    // Where EXAMPLE was given, which make_func! rejects
+    example_keyword: Option<Ident>,
     block: Block,
 }
 
@@ -89,76 +237,355 @@ impl Parse for MakeFunc {
         parser.part_two = false;
         let mut output = Type::Verbatim(quote::quote!(i64));
         let mut no_ok = false;
+        let mut skip_invalid = false;
+        let mut explain = None;
+        let mut mode = InputMode::default();
+        let mut order = None;
+        let mut example: Option<Example> = None;
+        let mut example_keyword = None;
 
-        let mut has_parser = false;
-        let mut has_output = false;
+        // Span of each option given, to point at duplicates and misuse
+        let mut seen: HashMap<String, Span> = HashMap::new();
 
         while input.peek(Ident) {
-            if let Ok(p) = input.fork().parse::<Parser>() {
-                input.parse::<Parser>().unwrap();
-                if has_parser {
-                    return Err(input.error("PARSER specfied multiple times"));
+            let keyword: Ident = input.fork().parse()?;
+            let name = keyword.to_string();
+            if seen.insert(name.clone(), keyword.span()).is_some() {
+                return Err(Error::new(
+                    keyword.span(),
+                    format!("{} specified multiple times", name),
+                ));
+            }
+
+            match name.as_str() {
+                "PARSER" => parser = input.parse::<Parser>()?.parser,
+                "OUTPUT" => output = input.parse::<Output>()?.output,
+                "INPUT" => mode = input.parse()?,
+                "ORDERED" | "SEQUENTIAL" => {
+                    if let Some(other) = ["ORDERED", "SEQUENTIAL"]
+                        .into_iter()
+                        .find(|other| *other != name && seen.contains_key(*other))
+                    {
+                        return Err(Error::new(
+                            keyword.span(),
+                            format!("{} conflicts with {}", name, other),
+                        ));
+                    }
+                    order = Some(input.parse()?);
                 }
-                has_parser = true;
-                parser = p.parser;
-            } else if let Ok(o) = input.fork().parse::<Output>() {
-                input.parse::<Output>().unwrap();
-                if has_output {
-                    return Err(input.error("OUTPUT specified multiple times"));
+                "EXPLAIN" => explain = Some(input.parse::<Explain>()?.explain),
+                "EXAMPLE" => {
+                    example = Some(input.parse()?);
+                    example_keyword = Some(keyword);
                 }
-                has_output = true;
-                output = o.output;
-            } else if input.fork().parse::<NoOkay>().is_ok() {
-                input.parse::<NoOkay>().unwrap();
-                if no_ok {
-                    return Err(input.error("NO_OKAY specified multiple times"));
+                "NO_OKAY" => {
+                    input.parse::<NoOkay>()?;
+                    no_ok = true;
+                }
+                "SKIP_INVALID" => {
+                    input.parse::<SkipInvalid>()?;
+                    skip_invalid = true;
+                }
+                _ => {
+                    return Err(Error::new(
+                        keyword.span(),
+                        format!(
+                            "unknown option {}, expected PARSER, OUTPUT, INPUT, NO_OKAY, SKIP_INVALID, ORDERED, SEQUENTIAL, EXPLAIN or EXAMPLE",
+                            keyword
+                        ),
+                    ))
                 }
-                no_ok = true;
-            } else {
-                return Err(input.error("Unexpected identifier encountered"));
             }
         }
+        if let Some(span) = seen.get("NO_OKAY").filter(|_| mode != InputMode::Lines) {
+            return Err(Error::new(*span, "NO_OKAY only applies to INPUT: LINES"));
+        }
+        if let Some(span) = seen.get("SKIP_INVALID") {
+            if mode != InputMode::Lines {
+                return Err(Error::new(
+                    *span,
+                    "SKIP_INVALID only applies to INPUT: LINES",
+                ));
+            }
+            if no_ok {
+                return Err(Error::new(
+                    *span,
+                    "SKIP_INVALID conflicts with NO_OKAY, whose lines always parse",
+                ));
+            }
+        }
+        if let Some(span) = seen
+            .get("ORDERED")
+            .or(seen.get("SEQUENTIAL"))
+            .filter(|_| mode == InputMode::Whole)
+        {
+            return Err(Error::new(
+                *span,
+                "ORDERED and SEQUENTIAL do not apply to INPUT: WHOLE",
+            ));
+        }
+
         let block: Block = input.parse()?;
         if !input.is_empty() {
             return Err(input.error("Unexpected trailing characters"));
         }
 
+        let mut default_example = day;
+        default_example.part_two = false;
+        let examples = example
+            .map(|example| example.cases)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, output)| ExampleCase {
+                name: name.unwrap_or_else(|| default_example.to_string()),
+                output,
+            })
+            .collect();
+
         Ok(Self {
             day,
             parser,
             output,
+            input: mode,
+            order: order.unwrap_or_default(),
             block,
+            examples,
+            example_keyword,
             no_ok,
+            skip_invalid,
+            explain,
         })
     }
 }
 
 pub fn make_func(input: TokenStream) -> TokenStream {
-    let make_func: MakeFunc = parse2(input).expect("Failed to parse MakeFunc");
+    let make_func: MakeFunc = match parse2(input) {
+        Ok(make_func) => make_func,
+        Err(err) => return err.to_compile_error(),
+    };
+    if let Some(keyword) = make_func.example_keyword {
+        return Error::new(keyword.span(), "EXAMPLE is only supported by aoc_day!")
+            .to_compile_error();
+    }
+    expand(make_func)
+}
 
+// The solution functions and their registration
+pub fn expand(make_func: MakeFunc) -> TokenStream {
     let day_name = format_ident!("{}", make_func.day.to_string());
     let parser_name = format_ident!("{}", make_func.parser.to_string());
     let output_type = make_func.output;
     let stmts = make_func.block.stmts;
 
-    let func_proto = quote::quote! {
-        pub fn #day_name(input: &[&str]) -> #output_type
+    let try_name = format_ident!("try_{}", day_name);
+    let parse_name = format_ident!("parse_{}", day_name);
+    let prepare_name = format_ident!("prepare_{}", day_name);
+    let examples: Vec<_> = make_func
+        .examples
+        .iter()
+        .map(|case| case.name.clone())
+        .collect();
+    let check_name = format_ident!("check_{}", day_name);
+    // Only days that skip lines have anything to check
+    let skips_lines = make_func.input == InputMode::Lines && make_func.skip_invalid;
+    let registration = make_func.day.register(
+        &try_name,
+        &parse_name,
+        skips_lines.then_some(&check_name),
+        make_func.explain.as_ref(),
+        &examples,
+    );
+
+    // Iterating over the input lines, and over a Vec of parsed items
+    let (iter, into_iter) = match make_func.order {
+        Order::Unordered | Order::Ordered => {
+            (quote::quote!(par_iter), quote::quote!(into_par_iter))
+        }
+        Order::Sequential => (quote::quote!(iter), quote::quote!(into_iter)),
     };
 
-    let input_line = if make_func.no_ok {
-        quote::quote! {
-            let input = input.into_par_iter().map(|line| parser::#parser_name::parse_line(line));
+    // Names the parser in parse errors
+    let context = parser_name.to_string();
+
+    // Everything that parses the input, leaving it in input for the body
+    let parse_input = match make_func.input {
+        InputMode::Lines => {
+            let lines = if make_func.no_ok {
+                quote::quote! {
+                    input.#iter().map(|line| parser::#parser_name::parse_line(line))
+                }
+            } else if make_func.skip_invalid {
+                quote::quote! {
+                    input
+                        .#iter()
+                        .filter_map(|line| parser::whole_line(line, parser::#parser_name::parse_line).ok())
+                }
+            } else {
+                quote::quote! {
+                    parser::parse_lines(input, #context, parser::#parser_name::parse_line)?.#into_iter()
+                }
+            };
+            // Only streamed lines need collecting to be indexed
+            let streamed = make_func.no_ok || make_func.skip_invalid;
+            if make_func.order == Order::Ordered && streamed {
+                quote::quote! {
+                    let input: Vec<_> = #lines.collect();
+                    let input = input.into_par_iter();
+                }
+            } else {
+                quote::quote! {
+                    let input = #lines;
+                }
+            }
         }
-    } else {
+        InputMode::Whole => quote::quote! {
+            let input = parser::#parser_name::parse_input(input)?;
+        },
+        InputMode::Sections => quote::quote! {
+            let input = parser::sections(input)
+                .#into_iter()
+                .map(|(offset, section)| {
+                    parser::#parser_name::parse_section(section)
+                        .map_err(|err| err.at_offset(offset))
+                })
+                .collect::<Result<Vec<_>, crate::AocError>>()?
+                .#into_iter();
+        },
+    };
+
+    let check = skips_lines.then(|| {
         quote::quote! {
-            let input = input.into_par_iter().map(|line| parser::#parser_name::parse_line(line).ok()).flatten();
+            pub fn #check_name(input: &[&str]) -> Result<(), crate::AocError> {
+                parser::check_lines(input, #context, parser::#parser_name::parse_line)
+            }
+        }
+    });
+
+    // The body is only emitted in prepare_dayN, every other function goes through it
+    let funcs = quote::quote! {
+        // Parses the input, the closure it returns is the rest of the solution
+        fn #prepare_name<'a>(
+            input: &'a [&'a str],
+        ) -> Result<impl FnOnce() -> #output_type + Send + 'a, crate::AocError> {
+            #parse_input
+            Ok(move || -> #output_type {
+                #(#stmts)*
+            })
+        }
+
+        pub fn #day_name(input: &[&str]) -> #output_type {
+            let solve = #prepare_name(input).unwrap_or_else(|err| panic!("{}", err));
+            solve()
+        }
+
+        #check
+
+        pub fn #try_name(input: &[&str]) -> Result<crate::Answer, crate::AocError> {
+            #parse_name(input)?.solve()
+        }
+
+        pub fn #parse_name<'a>(input: &'a [&'a str]) -> Result<crate::Parsed<'a>, crate::AocError> {
+            let solve = #prepare_name(input)?;
+            Ok(crate::Parsed::new(move || crate::IntoAnswer::into_answer(solve())))
         }
     };
 
     quote::quote! {
-        #func_proto {
-            #input_line
-            #(#stmts)*
-        }
+        #funcs
+
+        #registration
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InputMode, MakeFunc, Order};
+    use proc_macro2::TokenStream;
+    use std::str::FromStr;
+    use syn::parse2;
+    use test_case::test_case;
+
+    #[test_case("1; { 0 }" => (InputMode::Lines, Order::Unordered) ; "when default")]
+    #[test_case("1; ORDERED; { 0 }" => (InputMode::Lines, Order::Ordered) ; "when ordered")]
+    #[test_case("1; NO_OKAY; SEQUENTIAL; { 0 }" => (InputMode::Lines, Order::Sequential) ; "when sequential")]
+    #[test_case("5; INPUT: SECTIONS; SEQUENTIAL; { 0 }" => (InputMode::Sections, Order::Sequential) ; "when sequential sections")]
+    #[test_case("3; INPUT: WHOLE; { 0 }" => (InputMode::Whole, Order::Unordered) ; "when whole")]
+    fn modes(input: &str) -> (InputMode, Order) {
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_func: MakeFunc = parse2(ts).unwrap();
+        (make_func.input, make_func.order)
+    }
+
+    #[test_case("1; ORDERED; SEQUENTIAL; { 0 }" => "SEQUENTIAL conflicts with ORDERED" ; "when ordered and sequential")]
+    #[test_case("1; INPUT: WHOLE; ORDERED; { 0 }" => "ORDERED and SEQUENTIAL do not apply to INPUT: WHOLE" ; "when ordered whole")]
+    #[test_case("1; INPUT: WHOLE; NO_OKAY; { 0 }" => "NO_OKAY only applies to INPUT: LINES" ; "when whole without okay")]
+    #[test_case("1; INPUT: LINES; INPUT: WHOLE; { 0 }" => "INPUT specified multiple times" ; "when input twice")]
+    #[test_case("1; INPUT: ROWS; { 0 }" => "expected LINES, WHOLE or SECTIONS" ; "when unknown input")]
+    #[test_case("1; INPUT: SECTIONS; SKIP_INVALID; { 0 }" => "SKIP_INVALID only applies to INPUT: LINES" ; "when skipping sections")]
+    #[test_case("1; NO_OKAY; SKIP_INVALID; { 0 }" => "SKIP_INVALID conflicts with NO_OKAY, whose lines always parse" ; "when skipping without okay")]
+    #[test_case("1; OUTPT: i64; { 0 }" => "unknown option OUTPT, expected PARSER, OUTPUT, INPUT, NO_OKAY, SKIP_INVALID, ORDERED, SEQUENTIAL, EXPLAIN or EXAMPLE" ; "when unknown option")]
+    #[test_case("1p3; { 0 }" => "expected a day like 5 or 5p2" ; "when bad day")]
+    fn modes_errors(input: &str) -> String {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2::<MakeFunc>(ts).unwrap_err().to_string()
+    }
+
+    #[test_case("7; { 0 }" => Vec::<(String, String)>::new() ; "when none")]
+    #[test_case("7p2; EXAMPLE: 5905; { 0 }" => vec![(String::from("day7"), String::from("5905"))] ; "when default")]
+    #[test_case(r#"1p2; EXAMPLE: "day1p2" => 281; { 0 }"# => vec![(String::from("day1p2"), String::from("281"))] ; "when named")]
+    #[test_case(r#"1p2; EXAMPLE: ["a" => 1, "b" => "x"]; { 0 }"# => vec![
+        (String::from("a"), String::from("1")),
+        (String::from("b"), String::from("\"x\"")),
+    ] ; "when cases")]
+    fn examples(input: &str) -> Vec<(String, String)> {
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_func: MakeFunc = parse2(ts).unwrap();
+        make_func
+            .examples
+            .into_iter()
+            .map(|case| {
+                let output = case.output;
+                (case.name, quote::quote!(#output).to_string())
+            })
+            .collect()
+    }
+
+    #[test_case("2; SKIP_INVALID; { 0 }" => true ; "when skipping")]
+    #[test_case("2; { 0 }" => false ; "when lines")]
+    #[test_case("1; NO_OKAY; { 0 }" => false ; "when no okay")]
+    #[test_case("3; INPUT: WHOLE; { 0 }" => false ; "when whole")]
+    #[test_case("5; INPUT: SECTIONS; { 0 }" => false ; "when sections")]
+    fn check_lines(input: &str) -> bool {
+        let ts = TokenStream::from_str(input).unwrap();
+        super::make_func(ts).to_string().contains("fn check_day")
+    }
+
+    #[test_case("1; NO_OKAY; { 0 }" => false ; "when streamed")]
+    #[test_case("1; NO_OKAY; ORDERED; { 0 }" => true ; "when ordered")]
+    #[test_case("1; NO_OKAY; SEQUENTIAL; { 0 }" => false ; "when sequential")]
+    #[test_case("2; SKIP_INVALID; { 0 }" => false ; "when skipping")]
+    #[test_case("2; SKIP_INVALID; ORDERED; { 0 }" => true ; "when skipping ordered")]
+    fn collects(input: &str) -> bool {
+        let ts = TokenStream::from_str(input).unwrap();
+        super::make_func(ts).to_string().contains("collect ()")
+    }
+
+    #[test_case("2; { body() }" ; "when lines")]
+    #[test_case("1; NO_OKAY; SEQUENTIAL; { body() }" ; "when no okay")]
+    #[test_case("7; SKIP_INVALID; ORDERED; { body() }" ; "when skipping")]
+    #[test_case("3; INPUT: WHOLE; { body() }" ; "when whole")]
+    #[test_case("5; INPUT: SECTIONS; { body() }" ; "when sections")]
+    fn body_once(input: &str) {
+        let ts = TokenStream::from_str(input).unwrap();
+        let output = super::make_func(ts).to_string();
+        assert_eq!(1, output.matches("body ()").count(), "{}", output);
+    }
+
+    #[test]
+    fn make_func_examples_errors() {
+        let ts = TokenStream::from_str("7; EXAMPLE: 1; { 0 }").unwrap();
+        let output = super::make_func(ts).to_string();
+        assert!(output.contains("compile_error"), "{}", output);
+        assert!(output.contains("EXAMPLE is only supported by aoc_day!"));
     }
 }
@@ -1,31 +1,49 @@
 use crate::util::{
-    parse_block, parse_days, parse_input_path, AccessDays, AccessPath, Day, IdentMap,
+    parse_block, parse_days, parse_input_path, AccessDays, AccessPath, Day, IdentMap, DEFAULT_YEAR,
 };
 
 use phf::phf_map;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::format_ident;
 use std::collections::{HashMap, HashSet};
 use syn::{
     parse::{Parse, ParseStream, Result},
     parse2,
-    token::{FatArrow, Semi},
-    Lit, LitStr,
+    punctuated::Punctuated,
+    token::{Bracket, Comma, FatArrow, Semi},
+    Error, ExprPath, Lit, LitInt, LitStr,
 };
 
 // Make tests for advent of code 2023
+// DAYS is optional and defaults to every day in OUTPUTS, a test is also
+// generated that checks every registered solution has an expected output
+// here or examples declared with aoc_day!
 // Example:
 // make_tests! {
-//   INPUT_PATH: str;
-//   DAYS: [1, 1p2, ...];
+//   INPUT_PATH: str; // optional without OUTPUTS
+//   DAYS: [1, 1p2, ...]; // optional
 //   INPUT_OVERRIDES: {
 //       1p2 => "day1p2";
 //   };
-//   OUTPUTS: {
+//   OUTPUTS: { // optional
 //     1 => 1;
 //     1p2 => "test";
+//     2 => ["day2_a" => 1, "day2_b" => 2]; // one test per case, reads day2_a_ex.dat etc
 //   };
+//   REAL_INPUT_PATH: str; // optional, relative to the crate root
+//   REAL_OUTPUTS: { // optional, days not listed are checked against answers.toml
+//     1 => 54338;
+//   };
+//   REFERENCES: { // optional
+//     5p2 => crate::reference::day5p2;
+//   };
+//   REFERENCE_CASES: 100; // optional, inputs to check each reference on
+//   YEAR: 2023; // optional, the year of days written without one
 // }
+// Real input tests run every registered solution for YEAR on
+// <REAL_INPUT_PATH or $AOC_INPUT_DIR>/YYYY/dayN.dat at runtime, skipping missing files
+// Reference tests check try_dayN agrees with a slow reference solution, any
+// fn(&[&str]) returning something IntoAnswer, on small generated inputs
 
 // Day override specifier: day => str;
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -72,23 +90,128 @@ impl Parse for DayOverride {
     }
 }
 
+// Example case specifier: "name" => output
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ExampleCase {
+    pub name: String,
+    pub output: Lit,
+}
+
+impl ExampleCase {
+    // Test function name, the case name with anything not valid in an identifier replaced
+    pub fn test_name(&self) -> String {
+        let name: String = self
+            .name
+            .chars()
+            .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+            .collect();
+        if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+            format!("case_{}", name)
+        } else {
+            name
+        }
+    }
+
+    pub fn get_input_path(&self, inp_path: &str) -> String {
+        format!("{}/{}_ex.dat", inp_path, self.name)
+    }
+}
+
+impl Parse for ExampleCase {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: LitStr = input.parse()?;
+        input.parse::<FatArrow>()?;
+        let output: Lit = input.parse()?;
+
+        Ok(Self {
+            name: name.value(),
+            output,
+        })
+    }
+}
+
+// An example case along with its name's literal, to point errors at
+struct LitCase(LitStr, ExampleCase);
+
+impl Parse for LitCase {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: LitStr = input.fork().parse()?;
+        Ok(Self(name, input.parse()?))
+    }
+}
+
+// Expected output specifier: literal | ["name" => literal, ...]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Expected {
+    Single(Lit),
+    Cases(Vec<ExampleCase>),
+}
+
+impl Parse for Expected {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if !input.peek(Bracket) {
+            return Ok(Self::Single(input.parse()?));
+        }
+
+        let content;
+        let brackets = syn::bracketed!(content in input);
+        let cases = Punctuated::<LitCase, Comma>::parse_terminated(&content)?;
+        if cases.is_empty() {
+            return Err(Error::new(
+                brackets.span.join(),
+                "expected at least one example case",
+            ));
+        }
+
+        let mut names = HashSet::new();
+        if let Some(LitCase(name, case)) = cases
+            .iter()
+            .find(|LitCase(_, case)| !names.insert(case.test_name()))
+        {
+            return Err(Error::new(
+                name.span(),
+                format!("example case {} declared multiple times", case.name),
+            ));
+        }
+
+        Ok(Self::Cases(
+            cases.into_iter().map(|LitCase(_, case)| case).collect(),
+        ))
+    }
+}
+
 struct MakeTests {
     input_path: String,
     days: Vec<Day>,
     overrides: DayOverrides,
-    expected: HashMap<Day, Lit>,
+    expected: HashMap<Day, Expected>,
+    real_input_path: Option<String>,
+    real_expected: HashMap<Day, Lit>,
+    references: HashMap<Day, ExprPath>,
+    reference_cases: u64,
+    year: u16,
 }
 
+const DEFAULT_REFERENCE_CASES: u64 = 100;
+
+// day => T; entries in the order they were written, with each day's span
+type Outputs<T> = Vec<(Day, Span, T)>;
+
 #[derive(Default)]
 struct ParseData {
     input_path: Option<String>,
-    days: Option<Vec<Day>>,
+    days: Option<Vec<(Day, Span)>>,
     overrides: Option<DayOverrides>,
-    expected: Option<HashMap<Day, Lit>>,
+    expected: Option<Outputs<Expected>>,
+    real_input_path: Option<String>,
+    real_expected: Option<(Span, Outputs<Lit>)>,
+    references: Option<Outputs<ExprPath>>,
+    reference_cases: Option<u64>,
+    year: Option<u16>,
 }
 
 impl AccessDays for ParseData {
-    fn access_days(&mut self) -> &mut Option<Vec<Day>> {
+    fn access_days(&mut self) -> &mut Option<Vec<(Day, Span)>> {
         &mut self.days
     }
 }
@@ -100,64 +223,86 @@ impl AccessPath for ParseData {
 }
 
 fn parse_overrides(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
-    if state.overrides.is_some() {
-        Err(input.error("INPUT_OVERRIDES declared multiple times."))
-    } else {
-        let content;
-        syn::braced!(content in input);
+    let content;
+    syn::braced!(content in input);
+
+    let mut replacements: Vec<DayOverride> = vec![];
+    let mut semicolon = Ok(Semi::default()); // Start out with a phantom semi
+    while !content.is_empty() {
+        // If there's more input and the last token wasn't a semi
+        semicolon?;
+
+        let item = content.parse()?;
+        replacements.push(item);
+        semicolon = content.parse();
+    }
 
-        let mut replacements: Vec<DayOverride> = vec![];
-        let mut semicolon = Ok(Semi::default()); // Start out with a phantom semi
-        while !content.is_empty() {
-            // If there's more input and the last token wasn't a semi
-            semicolon?;
+    state.overrides = Some(DayOverrides(replacements));
+    Ok(())
+}
 
-            let item = content.parse()?;
-            replacements.push(item);
-            semicolon = content.parse();
+// Parse a block of day => T; entries
+fn parse_outputs<T: Parse>(input: &mut ParseStream) -> Result<Outputs<T>> {
+    let mut outputs: Outputs<T> = vec![];
+    let content;
+    syn::braced!(content in input);
+    let mut semicolon = Ok(Semi::default()); // Start out with a phantom semi
+    while !content.is_empty() {
+        // If there's more input and the last token wasn't a semi
+        semicolon?;
+
+        let span = content.span();
+        let day: Day = content.parse()?;
+        if outputs.iter().any(|(other, _, _)| *other == day) {
+            return Err(Error::new(
+                span,
+                format!("output declared multiple times for {}", day),
+            ));
         }
 
-        state.overrides = Some(DayOverrides(replacements));
-        Ok(())
+        content.parse::<FatArrow>()?;
+
+        let expected: T = content.parse()?;
+        outputs.push((day, span, expected));
+
+        semicolon = content.parse();
     }
+
+    Ok(outputs)
 }
 
 fn parse_expected(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
-    if state.expected.is_some() {
-        Err(input.error("OUTPUTS declared multiple times."))
-    } else {
-        state.expected = Some(HashMap::new());
-        let content;
-        syn::braced!(content in input);
-        let mut semicolon = Ok(Semi::default()); // Start out with a phantom semi
-        while !content.is_empty() {
-            // If there's more input and the last token wasn't a semi
-            semicolon?;
-
-            let day: Day = content.parse()?;
-            if state
-                .expected
-                .as_ref()
-                .is_some_and(|map| map.contains_key(&day))
-            {
-                return Err(input.error(format!(
-                    "output declared multiple times for {}",
-                    day,
-                )));
-            }
+    state.expected = Some(parse_outputs(input)?);
+    Ok(())
+}
 
-            content.parse::<FatArrow>()?;
+fn parse_real_input_path(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    let litstr: LitStr = input.parse()?;
+    state.real_input_path = Some(litstr.value());
+    Ok(())
+}
 
-            let lit: Lit = content.parse()?;
-            if let Some(map) = state.expected.as_mut() {
-                map.insert(day, lit);
-            }
+fn parse_real_expected(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    let span = input.span();
+    state.real_expected = Some((span, parse_outputs(input)?));
+    Ok(())
+}
 
-            semicolon = content.parse();
-        }
+fn parse_references(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    state.references = Some(parse_outputs(input)?);
+    Ok(())
+}
 
-        Ok(())
-    }
+fn parse_reference_cases(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    let cases: LitInt = input.parse()?;
+    state.reference_cases = Some(cases.base10_parse()?);
+    Ok(())
+}
+
+fn parse_year(input: &mut ParseStream, state: &mut ParseData) -> Result<()> {
+    let year: LitInt = input.parse()?;
+    state.year = Some(year.base10_parse()?);
+    Ok(())
 }
 
 static KEYWORDS: IdentMap<ParseData> = phf_map! {
@@ -165,45 +310,182 @@ static KEYWORDS: IdentMap<ParseData> = phf_map! {
     "DAYS" => parse_days,
     "INPUT_OVERRIDES" => parse_overrides,
     "OUTPUTS" => parse_expected,
+    "REAL_INPUT_PATH" => parse_real_input_path,
+    "REAL_OUTPUTS" => parse_real_expected,
+    "REFERENCES" => parse_references,
+    "REFERENCE_CASES" => parse_reference_cases,
+    "YEAR" => parse_year,
 };
 
+// Days written without a year go in year, which may make two entries the same day
+// The error points at the later of the two
+fn keys_in_year<T>(outputs: Outputs<T>, year: u16) -> Result<HashMap<Day, T>> {
+    let mut in_year = HashMap::new();
+    outputs.into_iter().try_for_each(|(day, span, value)| {
+        let day = day.in_year(year);
+        match in_year.insert(day, value) {
+            None => Ok(()),
+            Some(_) => Err(Error::new(
+                span,
+                format!(
+                    "output declared multiple times for {} in {}",
+                    day,
+                    day.year()
+                ),
+            )),
+        }
+    })?;
+    Ok(in_year)
+}
+
 impl Parse for MakeTests {
     fn parse(mut input: ParseStream) -> Result<Self> {
         let parse_state = parse_block(&mut input, &KEYWORDS, ParseData::default())?;
 
-        if let ParseData {
-            input_path: Some(input_path),
-            days: Some(days),
+        let ParseData {
+            input_path,
+            days,
             overrides,
-            expected: Some(expected),
-        } = parse_state
-        {
-            Ok(MakeTests {
-                input_path,
-                days,
-                overrides: overrides.unwrap_or_else(Default::default),
-                expected,
-            })
-        } else {
-            Err(input.error("Missing required fields in make_tests block."))
+            expected,
+            real_input_path,
+            real_expected,
+            references,
+            reference_cases,
+            year,
+        } = parse_state;
+
+        // Days written without a year are in YEAR
+        let year = year.unwrap_or(DEFAULT_YEAR);
+
+        let real_expected = match (&real_input_path, real_expected) {
+            (None, Some((span, _))) => {
+                return Err(Error::new(span, "REAL_OUTPUTS requires REAL_INPUT_PATH"))
+            }
+            (_, Some((_, outputs))) => Some(keys_in_year(outputs, year)?),
+            (_, None) => None,
+        };
+
+        let expected = keys_in_year(expected.unwrap_or_default(), year)?;
+        let days = match days {
+            Some(days) => {
+                let days: Vec<_> = days
+                    .into_iter()
+                    .map(|(day, span)| (day.in_year(year), span))
+                    .collect();
+                if let Some((day, span)) = days.iter().find(|(day, _)| !expected.contains_key(day))
+                {
+                    return Err(Error::new(
+                        *span,
+                        format!("{} is listed in DAYS but has no output in OUTPUTS", day),
+                    ));
+                }
+                days.into_iter().map(|(day, _)| day).collect()
+            }
+            None => {
+                let mut days: Vec<_> = expected.keys().copied().collect();
+                days.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                days
+            }
+        };
+        if input_path.is_none() && !days.is_empty() {
+            return Err(Error::new(
+                Span::call_site(),
+                "make_tests! needs an INPUT_PATH for its example tests",
+            ));
         }
+
+        Ok(MakeTests {
+            input_path: input_path.unwrap_or_default(),
+            days,
+            overrides: DayOverrides(
+                overrides
+                    .unwrap_or_default()
+                    .0
+                    .into_iter()
+                    .map(|day_override| DayOverride {
+                        day: day_override.day.in_year(year),
+                        ..day_override
+                    })
+                    .collect(),
+            ),
+            expected,
+            real_input_path,
+            real_expected: real_expected.unwrap_or_default(),
+            references: keys_in_year(references.unwrap_or_default(), year)?,
+            reference_cases: reference_cases.unwrap_or(DEFAULT_REFERENCE_CASES),
+            year,
+        })
     }
 }
 
 pub fn make_tests(input: TokenStream) -> TokenStream {
-    let ast: MakeTests = parse2(input).expect("Failed to parse make_tests AST");
+    let ast: MakeTests = match parse2(input) {
+        Ok(ast) => ast,
+        Err(err) => return err.to_compile_error(),
+    };
 
     let mut created_statics: HashSet<String> = HashSet::new();
-    ast.days
+    let year = ast.year;
+    let expected_days = ast
+        .expected
+        .keys()
+        .map(|day| day.to_tokens(quote::quote!(crate::Day)));
+    let registered = quote::quote! {
+        #[test]
+        fn registered_days_have_outputs() {
+            let expected = [#(#expected_days),*];
+            crate::solutions()
+                .into_iter()
+                .filter(|solution| solution.day.year == #year)
+                .for_each(|solution| {
+                assert!(
+                    expected.contains(&solution.day) || !solution.examples.is_empty(),
+                    "{} is registered but has no expected output",
+                    solution.day,
+                );
+            });
+        }
+    };
+
+    let real = ast
+        .real_input_path
+        .map(|path| real_tests(&path, ast.year, &ast.real_expected));
+    let references = reference_tests(&ast.references, ast.reference_cases);
+
+    let tests = ast
+        .days
         .into_iter()
         .map(|day| {
             let day_str = quote::format_ident!("{}", day.to_string());
+            let try_name = quote::format_ident!("try_{}", day.to_string());
             let day_static = ast.overrides.get_input_variable_name(day);
 
-            let lit = ast
-                .expected
-                .get(&day)
-                .expect("Did not find declared day in OUTPUTS");
+            // Every day has an output, checked while parsing
+            let lit = match &ast.expected[&day] {
+                Expected::Single(lit) => lit,
+                Expected::Cases(cases) => {
+                    let tests = cases.iter().map(|case| {
+                        let case_name = format_ident!("{}", case.test_name());
+                        let case_path = case.get_input_path(&ast.input_path);
+                        let lit = &case.output;
+                        quote::quote! {
+                            #[test]
+                            fn #case_name() {
+                                let input: Vec<&str> = include_str!(#case_path).lines().collect();
+                                let output = super::super::#try_name(&input)
+                                    .unwrap_or_else(|err| panic!("{}", err));
+                                assert_eq!(#lit, output);
+                            }
+                        }
+                    });
+
+                    return quote::quote! {
+                        mod #day_str {
+                            #(#tests)*
+                        }
+                    };
+                }
+            };
 
             if created_statics.contains(&day_static) {
                 let day_static = format_ident!("{}", day_static);
@@ -211,7 +493,8 @@ pub fn make_tests(input: TokenStream) -> TokenStream {
                     #[test]
                     fn #day_str() {
                         let input: Vec<&str> = #day_static.lines().collect();
-                        let output = super::#day_str(&input);
+                        let output = super::#try_name(&input)
+                            .unwrap_or_else(|err| panic!("{}", err));
                         assert_eq!(#lit, output);
                     }
                 }
@@ -225,18 +508,199 @@ pub fn make_tests(input: TokenStream) -> TokenStream {
                     #[test]
                     fn #day_str() {
                         let input: Vec<&str> = #day_static.lines().collect();
-                        let output = super::#day_str(&input);
+                        let output = super::#try_name(&input)
+                            .unwrap_or_else(|err| panic!("{}", err));
                         assert_eq!(#lit, output);
                     }
                 }
             }
         })
-        .collect::<TokenStream>()
+        .collect::<TokenStream>();
+
+    quote::quote! {
+        #tests
+        #registered
+        #real
+        #references
+    }
+}
+
+// Differential tests against reference solutions, one per day
+fn reference_tests(references: &HashMap<Day, ExprPath>, cases: u64) -> TokenStream {
+    if references.is_empty() {
+        return TokenStream::new();
+    }
+
+    let mut references: Vec<_> = references.iter().collect();
+    references.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let tests = references.into_iter().map(|(day, reference)| {
+        let day_str = format_ident!("{}", day.to_string());
+        let try_name = format_ident!("try_{}", day.to_string());
+        let day = day.to_tokens(quote::quote!(crate::Day));
+        quote::quote! {
+            #[test]
+            fn #day_str() {
+                let day = #day;
+                (0..#cases).for_each(|seed| {
+                    let lines = crate::generators::generate_small(day, seed)
+                        .unwrap_or_else(|| panic!("no generator for {}", day));
+                    let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+                    let expected = crate::IntoAnswer::into_answer(#reference(&input))
+                        .unwrap_or_else(|err| panic!("reference for {} failed: {}", day, err));
+                    let output = super::super::#try_name(&input)
+                        .unwrap_or_else(|err| panic!("{} failed on seed {}: {}", day, seed, err));
+                    assert_eq!(
+                        expected,
+                        output,
+                        "{} disagrees with its reference on seed {}, input:\n{}",
+                        day,
+                        seed,
+                        lines.join("\n"),
+                    );
+                });
+            }
+        }
+    });
+
+    quote::quote! {
+        mod reference {
+            #(#tests)*
+        }
+    }
+}
+
+// Tests against real inputs, which aren't committed so may be missing
+// Every registered solution is run, so days declared anywhere are covered
+fn real_tests(path: &str, year: u16, expected: &HashMap<Day, Lit>) -> TokenStream {
+    let mut expected: Vec<_> = expected.iter().collect();
+    expected.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+    let arms = expected.iter().map(|(day, lit)| {
+        let year = day.year();
+        let number = day.number;
+        let part_two = day.part_two;
+        let shown = quote::quote!(#lit).to_string();
+        quote::quote! {
+            (#year, #number, #part_two) => Some((#lit == *output, #shown)),
+        }
+    });
+    let days = expected
+        .iter()
+        .map(|(day, _)| day.to_tokens(quote::quote!(crate::Day)));
+
+    quote::quote! {
+        mod real {
+            use crate::answers::{Answers, Check, ANSWERS_FILE};
+
+            fn input_dir() -> ::std::path::PathBuf {
+                ::std::env::var_os("AOC_INPUT_DIR")
+                    .map(::std::path::PathBuf::from)
+                    .unwrap_or_else(|| {
+                        ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(#path)
+                    })
+            }
+
+            fn answers(dir: &::std::path::Path) -> Answers {
+                let path = dir.join(ANSWERS_FILE);
+                if path.exists() {
+                    Answers::load(path).unwrap_or_else(|err| panic!("{}", err))
+                } else {
+                    Default::default()
+                }
+            }
+
+            // Whether output matches REAL_OUTPUTS, and the expected literal
+            #[allow(clippy::match_single_binding)]
+            fn expected(
+                day: crate::Day,
+                output: &crate::Answer,
+            ) -> Option<(bool, &'static str)> {
+                match (day.year, day.number, day.part_two) {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+
+            #[test]
+            fn outputs_are_registered() {
+                [#(#days),*].into_iter().for_each(|day| {
+                    assert!(
+                        crate::solution(day).is_some(),
+                        "{} has a real output but no solution",
+                        day
+                    );
+                });
+            }
+
+            #[test]
+            fn registered() {
+                let dir = input_dir();
+                let answers = answers(&dir);
+                let (mut missing, mut unknown, mut failures) = (vec![], vec![], vec![]);
+                crate::solutions()
+                    .into_iter()
+                    .filter(|solution| solution.day.year == #year)
+                    .for_each(|solution| {
+                        let day = solution.day;
+                        let path = crate::inputs::input_path(&dir, day);
+                        let input = match ::std::fs::read_to_string(&path) {
+                            Ok(input) => input,
+                            Err(err) if err.kind() == ::std::io::ErrorKind::NotFound => {
+                                missing.push(day.to_string());
+                                return;
+                            }
+                            Err(err) => panic!("could not read {}: {}", path.display(), err),
+                        };
+                        let input: Vec<&str> = input.lines().collect();
+                        let output = match (solution.run)(&input) {
+                            Ok(output) => output,
+                            Err(err) => {
+                                failures.push(format!("{}: {}", day, err));
+                                return;
+                            }
+                        };
+
+                        match expected(day, &output) {
+                            Some((true, _)) => {}
+                            Some((false, lit)) => {
+                                failures.push(format!("{}: expected {}, got {}", day, lit, output))
+                            }
+                            None => match answers.check(day, &output) {
+                                Check::Correct => {}
+                                Check::Wrong(expected) => failures
+                                    .push(format!("{}: expected {}, got {}", day, expected, output)),
+                                Check::Unknown => unknown.push(day.to_string()),
+                            },
+                        }
+                    });
+
+                // libtest captures eprintln!, so write straight to stderr to be seen when passing
+                let mut summary = vec![];
+                if !missing.is_empty() {
+                    summary.push(format!(
+                        "skipped {}, no input in {}",
+                        missing.join(", "),
+                        dir.display()
+                    ));
+                }
+                if !unknown.is_empty() {
+                    summary.push(format!("didn't check {}, no known answer", unknown.join(", ")));
+                }
+                if !summary.is_empty() {
+                    use ::std::io::Write;
+                    let _ = writeln!(::std::io::stderr().lock(), "real inputs: {}", summary.join("; "));
+                }
+
+                assert!(failures.is_empty(), "{}", failures.join("\n"));
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{DayOverrides, MakeTests};
+    use super::{DayOverrides, ExampleCase, Expected, MakeTests};
 
     use super::{Day, DayOverride};
     use proc_macro2::TokenStream;
@@ -244,17 +708,69 @@ mod test {
     use syn::{parse2, Lit};
     use test_case::test_case;
 
-    #[test_case("1" => Day {number: 1, part_two: false})]
-    #[test_case("1p2" => Day {number: 1, part_two: true})]
-    #[test_case("10" => Day {number: 10, part_two: false})]
-    #[test_case("10p2" => Day {number: 10, part_two: true})]
+    #[test_case("1" => Day {year: None, number: 1, part_two: false})]
+    #[test_case("1p2" => Day {year: None, number: 1, part_two: true})]
+    #[test_case("10" => Day {year: None, number: 10, part_two: false})]
+    #[test_case("10p2" => Day {year: None, number: 10, part_two: true})]
+    #[test_case("2024:5p2" => Day {year: Some(2024), number: 5, part_two: true})]
     fn day(input: &str) -> Day {
         let ts = TokenStream::from_str(input).unwrap();
         parse2(ts).unwrap()
     }
 
-    #[test_case("1 => \"day1p2\"" => DayOverride {day: Day {number: 1, part_two: false}, replacement: String::from("day1p2")})]
-    #[test_case("1p2 => \"day1p2\"" => DayOverride {day: Day {number: 1, part_two: true}, replacement: String::from("day1p2")})]
+    #[test_case("23:5" => "expected a year like 2023" ; "when short year")]
+    #[test_case("2024p2:5" => "expected a year like 2023" ; "when year has a part")]
+    #[test_case("2024:" => "unexpected end of input, expected integer literal" ; "when no day")]
+    fn day_errors(input: &str) -> String {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2::<Day>(ts).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn year() {
+        let input = r#"INPUT_PATH: "a"; OUTPUTS: { 1 => 1; 2015:3 => 3; }; YEAR: 2024;"#;
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_tests: MakeTests = parse2(ts).unwrap();
+        assert_eq!(2024, make_tests.year);
+
+        let mut days: Vec<_> = make_tests
+            .days
+            .iter()
+            .map(|day| (day.year(), day.number))
+            .collect();
+        days.sort_unstable();
+        assert_eq!(vec![(2015, 3), (2024, 1)], days);
+    }
+
+    #[test]
+    fn year_written() {
+        let input = r#"INPUT_PATH: "a"; OUTPUTS: { 2023:5 => 1; 5 => 2; }; REFERENCES: { 2023:5 => a::b; }; YEAR: 2024;"#;
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_tests: MakeTests = parse2(ts).unwrap();
+
+        let mut days: Vec<_> = make_tests
+            .days
+            .iter()
+            .map(|day| (day.year(), day.number))
+            .collect();
+        days.sort_unstable();
+        assert_eq!(vec![(2023, 5), (2024, 5)], days);
+        let references: Vec<_> = make_tests.references.keys().map(|day| day.year()).collect();
+        assert_eq!(vec![2023], references);
+    }
+
+    #[test]
+    fn year_duplicate() {
+        let input = r#"INPUT_PATH: "a"; OUTPUTS: { 2024:5 => 1; 5 => 2; }; YEAR: 2024;"#;
+        let ts = TokenStream::from_str(input).unwrap();
+        assert_eq!(
+            "output declared multiple times for day5 in 2024",
+            parse2::<MakeTests>(ts).err().unwrap().to_string()
+        );
+    }
+
+    #[test_case("1 => \"day1p2\"" => DayOverride {day: Day {year: None, number: 1, part_two: false}, replacement: String::from("day1p2")})]
+    #[test_case("1p2 => \"day1p2\"" => DayOverride {day: Day {year: None, number: 1, part_two: true}, replacement: String::from("day1p2")})]
     fn day_override(input: &str) -> DayOverride {
         let ts = TokenStream::from_str(input).unwrap();
         parse2(ts).unwrap()
@@ -280,18 +796,22 @@ mod test {
         assert_eq!(
             vec![
                 Day {
+                    year: Some(2023),
                     number: 1,
                     part_two: false,
                 },
                 Day {
+                    year: Some(2023),
                     number: 1,
                     part_two: true,
                 },
                 Day {
+                    year: Some(2023),
                     number: 2,
                     part_two: false,
                 },
                 Day {
+                    year: Some(2023),
                     number: 2,
                     part_two: true,
                 },
@@ -301,6 +821,7 @@ mod test {
         assert_eq!(
             DayOverrides(vec![DayOverride {
                 day: Day {
+                    year: Some(2023),
                     number: 1,
                     part_two: true
                 },
@@ -322,38 +843,149 @@ mod test {
         let mut outputs = vec![
             (
                 Day {
+                    year: Some(2023),
                     number: 1,
                     part_two: false,
                 },
-                day1,
+                Expected::Single(day1),
             ),
             (
                 Day {
+                    year: Some(2023),
                     number: 1,
                     part_two: true,
                 },
-                day1p2,
+                Expected::Single(day1p2),
             ),
             (
                 Day {
+                    year: Some(2023),
                     number: 2,
                     part_two: false,
                 },
-                day2,
+                Expected::Single(day2),
             ),
             (
                 Day {
+                    year: Some(2023),
                     number: 2,
                     part_two: true,
                 },
-                day2p2,
+                Expected::Single(day2p2),
             ),
         ];
         outputs.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
 
-        let mut my_outputs: Vec<(Day, Lit)> = make_tests.expected.into_iter().collect();
+        let mut my_outputs: Vec<(Day, Expected)> = make_tests.expected.into_iter().collect();
         my_outputs.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
 
         assert_eq!(outputs, my_outputs);
     }
+
+    #[test_case("1" => Expected::Single(parse2(quote::quote!(1)).unwrap()) ; "single")]
+    #[test_case(r#"["day1p2_a" => 281, "day1p2_b" => "12",]"# => Expected::Cases(vec![
+        ExampleCase {name: String::from("day1p2_a"), output: parse2(quote::quote!(281)).unwrap()},
+        ExampleCase {name: String::from("day1p2_b"), output: parse2(quote::quote!("12")).unwrap()},
+    ]) ; "cases")]
+    fn expected(input: &str) -> Expected {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2(ts).unwrap()
+    }
+
+    #[test_case("[]" => "expected at least one example case" ; "when no cases")]
+    #[test_case(r#"["a" => 1, "a" => 2]"# => "example case a declared multiple times" ; "when duplicate case")]
+    #[test_case(r#"["a" 1]"# => "expected `=>`" ; "when no arrow")]
+    fn expected_errors(input: &str) -> String {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2::<Expected>(ts).unwrap_err().to_string()
+    }
+
+    #[test_case("day1p2_a" => "day1p2_a")]
+    #[test_case("day7-jokers" => "day7_jokers")]
+    #[test_case("1st" => "case_1st")]
+    fn test_name(name: &str) -> String {
+        let case = ExampleCase {
+            name: name.to_owned(),
+            output: parse2(quote::quote!(1)).unwrap(),
+        };
+        case.test_name()
+    }
+
+    #[test]
+    fn real() {
+        let input = r#"INPUT_PATH: "../inputs";
+        OUTPUTS: {
+            1 => 1;
+        };
+        REAL_INPUT_PATH: "bench_inputs";
+        REAL_OUTPUTS: {
+            1 => 54338;
+            1p2 => "53389";
+        };"#;
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_tests: MakeTests = parse2(ts).unwrap();
+
+        assert_eq!(
+            Some(String::from("bench_inputs")),
+            make_tests.real_input_path
+        );
+
+        let day1: Lit = parse2(quote::quote!(54338)).unwrap();
+        let day1p2: Lit = parse2(quote::quote!("53389")).unwrap();
+        let mut outputs = vec![
+            (
+                Day {
+                    year: Some(2023),
+                    number: 1,
+                    part_two: false,
+                },
+                day1,
+            ),
+            (
+                Day {
+                    year: Some(2023),
+                    number: 1,
+                    part_two: true,
+                },
+                day1p2,
+            ),
+        ];
+        outputs.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        let mut my_outputs: Vec<(Day, Lit)> = make_tests.real_expected.into_iter().collect();
+        my_outputs.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        assert_eq!(outputs, my_outputs);
+    }
+
+    #[test_case(r#"REFERENCES: { 6 => crate::reference::day6; };"# => (vec![(6, false, String::from("crate :: reference :: day6"))], 100) ; "when default cases")]
+    #[test_case(r#"REFERENCES: { 5p2 => reference::day5p2; }; REFERENCE_CASES: 10;"# => (vec![(5, true, String::from("reference :: day5p2"))], 10) ; "when cases")]
+    fn references(input: &str) -> (Vec<(u8, bool, String)>, u64) {
+        let ts = TokenStream::from_str(input).unwrap();
+        let make_tests: MakeTests = parse2(ts).unwrap();
+        let references = make_tests
+            .references
+            .into_iter()
+            .map(|(day, path)| (day.number, day.part_two, quote::quote!(#path).to_string()))
+            .collect();
+        (references, make_tests.reference_cases)
+    }
+
+    #[test_case(r#"INPUT_PATH: "a"; OUTPUTS: {}; REAL_OUTPUTS: { 1 => 1; };"# => "REAL_OUTPUTS requires REAL_INPUT_PATH" ; "when no real input path")]
+    #[test_case(r#"INPUT_PATH: "a"; OUTPUTS: {}; REAL_INPUT_PATH: "b"; REAL_OUTPUTS: { 1 => ["a" => 1]; };"# => "expected literal" ; "when real cases")]
+    #[test_case(r#"OUTPUTS: { 1 => 1; };"# => "make_tests! needs an INPUT_PATH for its example tests" ; "when outputs without input path")]
+    fn real_errors(input: &str) -> String {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2::<MakeTests>(ts).map(drop).unwrap_err().to_string()
+    }
+
+    #[test_case(r#"INPUT_PATH: "a"; DAYS: [1, 2]; OUTPUTS: { 1 => 1; };"# => "day2 is listed in DAYS but has no output in OUTPUTS" ; "when day without output")]
+    #[test_case(r#"INPUT_PATH: "a"; OUTPUTS: { 1 => 1; 1 => 2; };"# => "output declared multiple times for day1" ; "when output twice")]
+    #[test_case(r#"INPUT_PATH: "a"; INPUT_PATH: "b";"# => "INPUT_PATH declared multiple times" ; "when keyword twice")]
+    #[test_case(r#"REFERENCES: { 6 => 6; };"# => "expected identifier" ; "when reference not a path")]
+    #[test_case(r#"INPUT_PAHT: "a";"# => "unknown keyword INPUT_PAHT, expected one of DAYS, INPUT_OVERRIDES, INPUT_PATH, OUTPUTS, REAL_INPUT_PATH, REAL_OUTPUTS, REFERENCES, REFERENCE_CASES, YEAR" ; "when unknown keyword")]
+    fn errors(input: &str) -> String {
+        let ts = TokenStream::from_str(input).unwrap();
+        parse2::<MakeTests>(ts).map(drop).unwrap_err().to_string()
+    }
 }
@@ -1,19 +1,27 @@
 use phf::Map;
+use proc_macro2::{Span, TokenStream};
+use quote::format_ident;
+use std::collections::HashSet;
 use syn::{
     parse::{Parse, ParseStream, Result},
-    token::{Colon, Comma, Semi},
-    Ident, LitInt, LitStr,
+    token::{Colon, Comma, PathSep, Semi},
+    Error, ExprPath, Ident, LitInt, LitStr,
 };
 
 pub type IdentMap<T> = Map<&'static str, fn(&mut ParseStream, &mut T) -> Result<()>>;
 
-// Day specifier: [12]?[0-9](p2)?
+// Day specifier: (YYYY:)?[12]?[0-9](p2)?, the year defaults to 2023
+// Displays as the day's function name, which doesn't include the year
 #[derive(PartialEq, PartialOrd, Eq, Copy, Clone, Debug, Hash)]
 pub struct Day {
+    // None when no year was written, make_tests! puts those days in its YEAR
+    pub year: Option<u16>,
     pub number: u8,
     pub part_two: bool,
 }
 
+pub const DEFAULT_YEAR: u16 = 2023;
+
 impl std::fmt::Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.part_two {
@@ -25,26 +33,92 @@ impl std::fmt::Display for Day {
 }
 
 impl Day {
-    pub fn to_bench_name(self) -> String {
-        if self.part_two {
-            format!("day {} p2", self.number)
-        } else {
-            format!("day {}", self.number)
+    pub fn year(self) -> u16 {
+        self.year.unwrap_or(DEFAULT_YEAR)
+    }
+
+    // The day in year, unless it was written with a year of its own
+    pub fn in_year(self, year: u16) -> Self {
+        Self {
+            year: Some(self.year.unwrap_or(year)),
+            ..self
+        }
+    }
+
+    // Expression for the advent2023::Day at path, e.g. crate::Day
+    pub fn to_tokens(self, path: TokenStream) -> TokenStream {
+        let year = self.year();
+        let number = self.number;
+        let part_two = self.part_two;
+        quote::quote!(#path::in_year(#year, #number, #part_two))
+    }
+
+    // Add the try_dayN and parse_dayN functions for this day to advent2023::SOLUTIONS,
+    // along with check_dayN for days that skip lines, the day's EXPLAIN function
+    // and the names of the examples declared for it
+    pub fn register(
+        self,
+        func: &Ident,
+        parse: &Ident,
+        check: Option<&Ident>,
+        explain: Option<&ExprPath>,
+        examples: &[String],
+    ) -> TokenStream {
+        let day = self.to_tokens(quote::quote!(crate::Day));
+        let check = match check {
+            Some(check) => quote::quote!(Some(#check)),
+            None => quote::quote!(None),
+        };
+        let explain = match explain {
+            Some(explain) => quote::quote!(Some(#explain)),
+            None => quote::quote!(None),
+        };
+        let static_name = format_ident!("SOLUTION_{}", self.to_string().to_uppercase());
+
+        quote::quote! {
+            #[::linkme::distributed_slice(crate::SOLUTIONS)]
+            static #static_name: crate::Solution = crate::Solution {
+                day: #day,
+                run: #func,
+                parse: #parse,
+                check_lines: #check,
+                explain: #explain,
+                examples: &[#(#examples),*],
+            };
         }
     }
 }
 
 impl Parse for Day {
     fn parse(input: ParseStream) -> Result<Self> {
-        let number: LitInt = input.parse()?;
-        let part_two = number.suffix() == "p2";
+        let mut number: LitInt = input.parse()?;
+        let mut year = None;
+        if input.peek(Colon) && !input.peek(PathSep) {
+            input.parse::<Colon>()?;
+            let written: u16 = number.base10_parse()?;
+            if !(2015..10000).contains(&written) || !number.suffix().is_empty() {
+                return Err(Error::new(number.span(), "expected a year like 2023"));
+            }
+            year = Some(written);
+            number = input.parse()?;
+        }
+
+        let part_two = match number.suffix() {
+            "" => false,
+            "p2" => true,
+            _ => return Err(Error::new(number.span(), "expected a day like 5 or 5p2")),
+        };
         let number: u8 = number.base10_parse()?;
-        Ok(Self { number, part_two })
+        Ok(Self {
+            year,
+            number,
+            part_two,
+        })
     }
 }
 
 pub trait AccessDays {
-    fn access_days(&mut self) -> &mut Option<Vec<Day>>;
+    fn access_days(&mut self) -> &mut Option<Vec<(Day, Span)>>;
 }
 
 pub trait AccessPath {
@@ -52,53 +126,61 @@ pub trait AccessPath {
 }
 
 pub fn parse_input_path<T: AccessPath>(input: &mut ParseStream, state: &mut T) -> Result<()> {
-    let path = state.access_path();
-    if path.is_some() {
-        Err(input.error("INPUT_PATH declared multiple times."))
-    } else {
-        let litstr: LitStr = input.parse()?;
-        *path = Some(litstr.value());
-        Ok(())
-    }
+    let litstr: LitStr = input.parse()?;
+    *state.access_path() = Some(litstr.value());
+    Ok(())
 }
 
+// Days are kept with their spans to point errors at them later
 pub fn parse_days<T: AccessDays>(input: &mut ParseStream, state: &mut T) -> Result<()> {
-    let state_days = state.access_days();
-    if state_days.is_some() {
-        Err(input.error("DAYS declared multiple times."))
-    } else {
-        let content;
-        syn::bracketed!(content in input);
-
-        let mut days: Vec<Day> = vec![];
-        let mut comma = Ok(Comma::default()); // Start out with a phantom comma
-        while !content.is_empty() {
-            // If there's more input and the last token wasn't a comma
-            comma?;
-
-            let day = content.parse()?;
-            days.push(day);
-            comma = content.parse();
-        }
-
-        *state_days = Some(days);
-        Ok(())
+    let content;
+    syn::bracketed!(content in input);
+
+    let mut days: Vec<(Day, Span)> = vec![];
+    let mut comma = Ok(Comma::default()); // Start out with a phantom comma
+    while !content.is_empty() {
+        // If there's more input and the last token wasn't a comma
+        comma?;
+
+        let span = content.span();
+        let day = content.parse()?;
+        days.push((day, span));
+        comma = content.parse();
     }
+
+    *state.access_days() = Some(days);
+    Ok(())
 }
 
 // Parse a block of IDENT: <anything>; using a map of IDENT -> parsers
 // parses the following semicolon as well, parser shouldn't
+// Each IDENT may only appear once
 pub fn parse_block<T>(input: &mut ParseStream, map: &IdentMap<T>, mut init: T) -> Result<T> {
+    let mut seen = HashSet::new();
     while !input.is_empty() {
         let ident: Ident = input.parse()?;
-        input.parse::<Colon>()?;
-
-        if let Some(func) = map.get(&ident.to_string()) {
-            func(input, &mut init)?;
-        } else {
-            return Err(input.error(format!("Unknown identifier encountered: {}", ident)));
+        let Some(func) = map.get(&ident.to_string()) else {
+            let mut expected: Vec<_> = map.keys().copied().collect();
+            expected.sort_unstable();
+            return Err(Error::new(
+                ident.span(),
+                format!(
+                    "unknown keyword {}, expected one of {}",
+                    ident,
+                    expected.join(", ")
+                ),
+            ));
+        };
+        if !seen.insert(ident.to_string()) {
+            return Err(Error::new(
+                ident.span(),
+                format!("{} declared multiple times", ident),
+            ));
         }
 
+        input.parse::<Colon>()?;
+        func(input, &mut init)?;
+
         input.parse::<Semi>()?;
     }
 
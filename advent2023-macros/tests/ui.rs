@@ -0,0 +1,9 @@
+// Compile errors from the macros, locked in with their spans
+// After changing a message, update the .stderr files with TRYBUILD=overwrite
+// tests/ui/pass holds expansions no day uses, built and run against the real crate
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,8 @@
+use advent2023_macros::aoc_day;
+
+aoc_day! {
+    1;
+    { input.sum() }
+}
+
+fn main() {}
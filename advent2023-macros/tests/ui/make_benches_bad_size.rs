@@ -0,0 +1,8 @@
+use advent2023_macros::make_benches;
+
+make_benches! {
+    INPUT_PATH: "bench_inputs";
+    GENERATED_SIZE: "100";
+}
+
+fn main() {}
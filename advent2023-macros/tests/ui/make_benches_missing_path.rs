@@ -0,0 +1,7 @@
+use advent2023_macros::make_benches;
+
+make_benches! {
+    GENERATED_SIZE: 100;
+}
+
+fn main() {}
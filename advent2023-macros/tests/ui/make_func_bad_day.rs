@@ -0,0 +1,8 @@
+use advent2023_macros::make_func;
+
+make_func! {
+    1p3;
+    { input.sum() }
+}
+
+fn main() {}
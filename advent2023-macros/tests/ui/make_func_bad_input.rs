@@ -0,0 +1,9 @@
+use advent2023_macros::make_func;
+
+make_func! {
+    1;
+    INPUT: ROWS;
+    { input.sum() }
+}
+
+fn main() {}
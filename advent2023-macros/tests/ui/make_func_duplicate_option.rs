@@ -0,0 +1,10 @@
+use advent2023_macros::make_func;
+
+make_func! {
+    1;
+    INPUT: LINES;
+    INPUT: WHOLE;
+    { input.sum() }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use advent2023_macros::make_func;
+
+make_func! {
+    3;
+    INPUT: WHOLE;
+    ORDERED;
+    { input.sum() }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    OUTPUTS: {
+        1p2 => ["day1p2" => 281, "day1p2" => 179];
+    };
+}
+
+fn main() {}
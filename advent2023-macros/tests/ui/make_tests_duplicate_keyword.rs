@@ -0,0 +1,8 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    INPUT_PATH: "../bench_inputs";
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    OUTPUTS: {
+        1 => 142;
+        1 => 143;
+    };
+}
+
+fn main() {}
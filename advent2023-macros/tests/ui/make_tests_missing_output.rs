@@ -0,0 +1,11 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    DAYS: [1, 2];
+    OUTPUTS: {
+        1 => 142;
+    };
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    REAL_OUTPUTS: {
+        1 => 54338;
+    };
+}
+
+fn main() {}
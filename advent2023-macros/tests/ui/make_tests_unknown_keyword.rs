@@ -0,0 +1,10 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    OUTPUT: {
+        1 => 142;
+    };
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use advent2023_macros::make_tests;
+
+make_tests! {
+    INPUT_PATH: "../inputs";
+    OUTPUTS: {
+        2024:5 => 35;
+        5 => 36;
+    };
+    YEAR: 2024;
+}
+
+fn main() {}
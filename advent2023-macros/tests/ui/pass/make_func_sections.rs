@@ -0,0 +1,51 @@
+// INPUT: SECTIONS against the real crate, no day uses it yet
+use advent2023::{Answer, AocError, Day, IntoAnswer, Parsed, Solution, SOLUTIONS};
+use advent2023_macros::make_func;
+use rayon::prelude::*;
+
+mod parser {
+    pub use advent2023::parser::sections;
+
+    pub mod day9 {
+        use advent2023::{
+            parser::{parse_int, parse_lines, IResult},
+            AocError,
+        };
+
+        pub fn parse_line(input: &str) -> IResult<'_, i64> {
+            parse_int(input)
+        }
+
+        // The sum of a section's numbers
+        pub fn parse_section(section: &[&str]) -> Result<i64, AocError> {
+            Ok(parse_lines(section, "day9", parse_line)?.into_iter().sum())
+        }
+    }
+}
+
+make_func! {
+    2015:9;
+    INPUT: SECTIONS;
+    { input.max().unwrap_or(0) }
+}
+
+make_func! {
+    2015:9p2;
+    INPUT: SECTIONS;
+    SEQUENTIAL;
+    { input.fold(0, |acc, sum| acc * 100 + sum) }
+}
+
+fn main() {
+    let input = ["1", "2", "", "3", "", "", "4", "5"];
+    assert_eq!(9, day9(&input));
+    assert_eq!(30309, day9p2(&input));
+
+    // Parse errors point at the line in the whole input, not in the section
+    match try_day9(&["1", "", "2", "x"]) {
+        Err(AocError::Parse(err)) => assert_eq!((4, 1), (err.line, err.column)),
+        res => panic!("{:?}", res),
+    }
+
+    assert!(advent2023::solution(Day::in_year(2015, 9, true)).is_some());
+}
@@ -0,0 +1,199 @@
+use crate::AocError;
+use num_bigint::BigInt;
+use std::fmt;
+
+// What every solution produces. Numeric answers compare by value regardless
+// of which variant holds them, so 5i64 == 5u128 and both equal the literal 5
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Big(BigInt),
+    Str(String),
+}
+
+impl Answer {
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, Self::Str(_))
+    }
+
+    // Numeric answers as i128 when they fit, which is everything but huge u128s and BigInts
+    pub fn to_i128(&self) -> Option<i128> {
+        match self {
+            Self::I64(num) => Some(*num as i128),
+            Self::U64(num) => Some(*num as i128),
+            Self::I128(num) => Some(*num),
+            Self::U128(num) => i128::try_from(*num).ok(),
+            Self::Big(num) => i128::try_from(num).ok(),
+            Self::Str(_) => None,
+        }
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Self::I64(num) => Some((*num).into()),
+            Self::U64(num) => Some((*num).into()),
+            Self::I128(num) => Some((*num).into()),
+            Self::U128(num) => Some((*num).into()),
+            Self::Big(num) => Some(num.clone()),
+            Self::Str(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::I64(num) => num.fmt(f),
+            Self::U64(num) => num.fmt(f),
+            Self::I128(num) => num.fmt(f),
+            Self::U128(num) => num.fmt(f),
+            Self::Big(num) => num.fmt(f),
+            Self::Str(s) => s.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Str(left), Self::Str(right)) => left == right,
+            (Self::Str(_), _) | (_, Self::Str(_)) => false,
+            _ => match (self.to_i128(), other.to_i128()) {
+                (Some(left), Some(right)) => left == right,
+                _ => self.to_bigint() == other.to_bigint(),
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! impl_from_int {
+    ($variant:ident($inner:ty): $($from:ty),*) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(num: $from) -> Self {
+                    Self::$variant(num as $inner)
+                }
+            }
+
+            impl PartialEq<$from> for Answer {
+                fn eq(&self, other: &$from) -> bool {
+                    match i128::try_from(*other).ok() {
+                        Some(other) => self.to_i128() == Some(other),
+                        None => self.to_bigint() == Some(BigInt::from(*other)),
+                    }
+                }
+            }
+
+            impl PartialEq<Answer> for $from {
+                fn eq(&self, other: &Answer) -> bool {
+                    other == self
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(I64(i64): i8, i16, i32, i64, isize);
+impl_from_int!(U64(u64): u8, u16, u32, u64, usize);
+impl_from_int!(I128(i128): i128);
+impl_from_int!(U128(u128): u128);
+
+impl From<BigInt> for Answer {
+    fn from(num: BigInt) -> Self {
+        Self::Big(num)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_owned())
+    }
+}
+
+// What a solution body may return, anything Answer converts from or a Result of one
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, AocError>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, AocError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, AocError> {
+    fn into_answer(self) -> Result<Answer, AocError> {
+        self.map(Into::into)
+    }
+}
+
+// Strings compare against the displayed answer, which lets numbers too large
+// for an integer literal be checked against a string literal
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Self::Str(s) => s == other,
+            num => other.parse::<BigInt>().ok() == num.to_bigint(),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+    use num_bigint::BigInt;
+    use test_case::test_case;
+
+    #[test_case(Answer::I64(-5) => "-5")]
+    #[test_case(Answer::U64(5) => "5")]
+    #[test_case(Answer::U128(u128::MAX) => "340282366920938463463374607431768211455")]
+    #[test_case(Answer::Big(BigInt::from(u128::MAX) * 10) => "3402823669209384634633746074317682114550")]
+    #[test_case(Answer::from("hi") => "hi")]
+    fn display(answer: Answer) -> String {
+        answer.to_string()
+    }
+
+    #[test_case(Answer::I64(5), Answer::U64(5) => true)]
+    #[test_case(Answer::I128(5), Answer::Big(5.into()) => true)]
+    #[test_case(Answer::I64(-1), Answer::U64(u64::MAX) => false)]
+    #[test_case(Answer::I64(5), Answer::from("5") => false ; "when number and string")]
+    #[test_case(Answer::from("a"), Answer::from("a") => true ; "when strings")]
+    fn eq(left: Answer, right: Answer) -> bool {
+        left == right
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(142, Answer::I64(142));
+        assert_eq!(Answer::U128(1 << 100), 1u128 << 100);
+        assert_eq!("hi", Answer::from(String::from("hi")));
+        assert_eq!(
+            "3402823669209384634633746074317682114550",
+            Answer::Big(BigInt::from(u128::MAX) * 10)
+        );
+        assert_ne!(-1, Answer::U64(u64::MAX));
+    }
+}
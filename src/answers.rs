@@ -0,0 +1,107 @@
+// Known answers for real puzzle inputs, stored as a flat TOML table
+// Example answers.toml:
+// 1 = 54338
+// 1p2 = 53389
+// 7 = "a string or a number too large for an i64"
+use crate::{Answer, AocError, Day};
+use std::{collections::BTreeMap, fs, path::Path};
+use toml::{Table, Value};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Check {
+    Correct,
+    Wrong(Answer),
+    Unknown,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Answers(BTreeMap<Day, Answer>);
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let table: Table = input
+            .parse()
+            .map_err(|err| AocError::input("answers", format!("{}", err)))?;
+
+        table
+            .into_iter()
+            .map(|(key, value)| {
+                let day: Day = key
+                    .parse()
+                    .map_err(|err| AocError::input("answers", format!("{}", err)))?;
+                let answer = match value {
+                    Value::Integer(num) => Answer::from(num),
+                    Value::String(s) => Answer::from(s),
+                    value => {
+                        return Err(AocError::input(
+                            "answers",
+                            format!("{} must be an integer or a string, not {}", key, value),
+                        ))
+                    }
+                };
+                Ok((day, answer))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+        let input = fs::read_to_string(path).map_err(|err| {
+            AocError::input(
+                "answers",
+                format!("could not read {}: {}", path.display(), err),
+            )
+        })?;
+        Self::parse(&input)
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.0.get(&day)
+    }
+
+    // A stored string matches a numeric answer that displays the same way
+    pub fn check(&self, day: Day, answer: &Answer) -> Check {
+        match self.get(day) {
+            None => Check::Unknown,
+            Some(Answer::Str(expected)) if answer == expected.as_str() => Check::Correct,
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong(expected.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answers, Check};
+    use crate::{Answer, Day};
+    use test_case::test_case;
+
+    const ANSWERS: &str = r#"
+        1 = 142
+        1p2 = "281"
+        7 = "hello"
+        "2015:1" = 74
+    "#;
+
+    #[test_case(Day::new(1, false), Answer::I64(142) => Check::Correct ; "when integer")]
+    #[test_case(Day::new(1, false), Answer::U128(142) => Check::Correct ; "when other integer type")]
+    #[test_case(Day::new(1, false), Answer::I64(143) => Check::Wrong(Answer::I64(142)) ; "when wrong")]
+    #[test_case(Day::new(1, true), Answer::I64(281) => Check::Correct ; "when numeric string")]
+    #[test_case(Day::new(7, false), Answer::from("hello") => Check::Correct ; "when string")]
+    #[test_case(Day::new(2, false), Answer::I64(8) => Check::Unknown ; "when missing")]
+    #[test_case(Day::in_year(2015, 1, false), Answer::I64(74) => Check::Correct ; "when other year")]
+    fn check(day: Day, answer: Answer) -> Check {
+        Answers::parse(ANSWERS).unwrap().check(day, &answer)
+    }
+
+    #[test_case("1 = 1.5" ; "when float")]
+    #[test_case("0 = 1" ; "when bad day")]
+    #[test_case("1 = " ; "when invalid toml")]
+    #[should_panic]
+    fn parse_panics(input: &str) {
+        Answers::parse(input).unwrap();
+    }
+}
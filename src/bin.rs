@@ -1,13 +1,1019 @@
-use clap::Parser;
-
-include!(concat!(env!("OUT_DIR"), "/generate_days.dat"));
+use advent2023::{
+    answers::{self, Answers, Check},
+    inputs, parse_year, parser,
+    timing::{self, Stats},
+    types::Almanac,
+    Answer, AocError, Day, ParseDayError, Solution, DEFAULT_YEAR,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    any::Any,
+    env,
+    ffi::OsString,
+    fmt,
+    ops::{Range, RangeInclusive},
+    panic,
+    path::PathBuf,
+    process::ExitCode,
+    slice,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    name: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+    /// Year of days given without one, and the only year run by all, check and list
+    /// [default: 2023, or every year for all, check and list]. Also given as aoc YYYY ...
+    /// Like --format, it goes after any subcommand
+    #[arg(long, value_parser = parse_year_arg, global = true)]
+    year: Option<u16>,
+}
+
+fn parse_year_arg(s: &str) -> Result<u16, String> {
+    parse_year(s).ok_or_else(|| format!("invalid year {:?}", s))
+}
+
+// A day as given on the command line, e.g. 5p2 or 2023:5p2
+// Days without a year are in --year, which isn't known until everything is parsed
+#[derive(Clone)]
+struct DayArg(String);
+
+impl FromStr for DayArg {
+    type Err = ParseDayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Day::parse_in_year(s, DEFAULT_YEAR).map(|_| Self(s.to_owned()))
+    }
+}
+
+impl DayArg {
+    fn day(&self, year: Option<u16>) -> Day {
+        Day::parse_in_year(&self.0, year.unwrap_or(DEFAULT_YEAR))
+            .expect("checked when the argument was parsed")
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    /// One object per run, or a list of objects for multi-day commands
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every registered day and print a summary table
+    All {
+        #[command(flatten)]
+        inputs: InputArgs,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Run every registered day in a range of day numbers in one year, e.g. 1..=6
+    Range {
+        #[arg(value_parser = parse_range)]
+        days: RangeInclusive<u8>,
+        #[command(flatten)]
+        inputs: InputArgs,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Run every registered day and compare against the known answers, same as all --check
+    Check {
+        #[command(flatten)]
+        inputs: InputArgs,
+        /// Known answers file [default: <INPUT_DIR>/answers.toml]
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Run one day repeatedly in-process and report timing statistics
+    Time {
+        /// Day to time, e.g. 5, 5p2 or 2023:5p2
+        name: DayArg,
+        /// Puzzle input file [default: <INPUT_DIR>/dayN.dat]
+        #[arg(conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Number of timed runs
+        #[arg(short = 'n', long, default_value_t = 100)]
+        runs: usize,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+    /// List every registered day
+    List,
+    /// Translate numbers or ranges from one category to another, day 5 only
+    Query {
+        /// Day whose input to query, e.g. 5
+        name: DayArg,
+        /// Numbers or ranges like 79..93 to translate
+        #[arg(required = true, allow_negative_numbers = true)]
+        values: Vec<QueryValue>,
+        /// Category the values are in, e.g. seed
+        #[arg(long)]
+        from: String,
+        /// Category to translate them to, e.g. location
+        #[arg(long)]
+        to: String,
+        /// Puzzle input file [default: <INPUT_DIR>/dayN.dat]
+        #[arg(long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        inputs: InputArgs,
+    },
+}
+
+// A number or a range of numbers to query, e.g. 81 or 79..93
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum QueryValue {
+    Number(i64),
+    Range(Range<i64>),
+}
+
+impl FromStr for QueryValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |num: &str| {
+            num.parse()
+                .map_err(|_| format!("expected a number or range like 79..93, not {:?}", s))
+        };
+        match s.split_once("..") {
+            Some((start, end)) => {
+                let range = parse(start)?..parse(end)?;
+                if range.is_empty() {
+                    return Err(format!("{:?} has no numbers in it", s));
+                }
+                Ok(Self::Range(range))
+            }
+            None => parse(s).map(Self::Number),
+        }
+    }
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(num) => num.fmt(f),
+            Self::Range(range) => write!(f, "{:?}", range),
+        }
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run, e.g. 5, 5p2 or 2023:5p2
+    #[arg(required = true)]
+    name: Option<DayArg>,
+    /// Puzzle input file, or - for stdin [default: <INPUT_DIR>/dayN.dat]
+    #[arg(conflicts_with = "example")]
+    input: Option<PathBuf>,
+    /// Also explain how the answer came about, for days that can, e.g. 5p2
+    #[arg(long)]
+    explain: bool,
+    #[command(flatten)]
+    inputs: InputArgs,
+    #[command(flatten)]
+    check: CheckArgs,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Run against the example inputs in inputs/YYYY/ instead
+    #[arg(long)]
+    example: bool,
+    /// Directory holding real puzzle inputs named YYYY/dayN.dat, or dayN.dat for 2023
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = inputs::DEFAULT_INPUT_DIR)]
+    input_dir: PathBuf,
+    /// Fail on the first line a SKIP_INVALID day can't parse instead of skipping it
+    #[arg(long)]
+    strict: bool,
+}
+
+impl InputArgs {
+    fn path(&self, day: Day) -> PathBuf {
+        if self.example {
+            inputs::example_path(inputs::EXAMPLE_DIR, day)
+        } else {
+            inputs::input_path(&self.input_dir, day)
+        }
+    }
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Compare answers against the known answers file
+    #[arg(long)]
+    check: bool,
+    /// Known answers file [default: <INPUT_DIR>/answers.toml]
+    #[arg(long, requires = "check")]
+    answers: Option<PathBuf>,
+}
+
+impl CheckArgs {
+    fn load(&self, inputs: &InputArgs) -> Result<Option<Answers>, ExitCode> {
+        if !self.check {
+            return Ok(None);
+        }
+
+        let path = self
+            .answers
+            .clone()
+            .unwrap_or_else(|| inputs.input_dir.join(answers::ANSWERS_FILE));
+        Answers::load(path).map(Some).map_err(|err| {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        })
+    }
+}
+
+// Accepts 1..=6, 1..7, 3.., ..=4 or a single day number
+fn parse_range(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_bound = |bound: &str| -> Result<u8, String> {
+        match bound.parse() {
+            Ok(0) => Err(String::from("days start at 1")),
+            Ok(day) => Ok(day),
+            Err(_) => Err(format!("invalid day number {:?}", bound)),
+        }
+    };
+    // Bounds left out of a range default to the first or last day
+    let parse_or = |bound: &str, default: u8| {
+        if bound.is_empty() {
+            Ok(default)
+        } else {
+            parse_bound(bound)
+        }
+    };
+
+    let Some((start, end)) = s.split_once("..") else {
+        let day = parse_bound(s)?;
+        return Ok(day..=day);
+    };
+    let start = parse_or(start, 1)?;
+    let range = match end.strip_prefix('=') {
+        Some(end) => start..=parse_bound(end)?,
+        None => start..=parse_or(end, 26)? - 1,
+    };
+    if range.is_empty() {
+        return Err(format!("{:?} has no days in it", s));
+    }
+    Ok(range)
+}
+
+enum RunError {
+    Read(String),
+    Solve(AocError),
+    Panic(String),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(msg) | Self::Panic(msg) => msg.fmt(f),
+            Self::Solve(err) => err.fmt(f),
+        }
+    }
+}
+
+struct Report {
+    day: Day,
+    // None when reading from stdin
+    input: Option<PathBuf>,
+    result: Result<Answer, RunError>,
+    check: Option<Check>,
+    // From the day's explain function, when asked for and there was an answer
+    explanation: Option<Result<String, RunError>>,
+    parse: Duration,
+    solve: Duration,
+}
+
+impl Report {
+    fn failed(&self) -> bool {
+        self.result.is_err() || matches!(self.check, Some(Check::Wrong(_)))
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        (*msg).to_owned()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "solution panicked".to_owned()
+    }
+}
+
+// Read from path, or stdin when there is no path
+fn read_input(path: Option<&PathBuf>) -> Result<Vec<String>, RunError> {
+    match path {
+        Some(path) => inputs::read_lines(path),
+        None => inputs::read_stdin(),
+    }
+    .map_err(|err| {
+        let source = path.map_or_else(|| "stdin".to_owned(), |p| p.display().to_string());
+        RunError::Read(format!("could not read {}: {}", source, err))
+    })
+}
+
+// With strict, lines the day would skip are errors
+fn check_lines(solution: &Solution, input: &[&str], strict: bool) -> Result<(), AocError> {
+    match solution.check_lines {
+        Some(check) if strict => check(input),
+        _ => Ok(()),
+    }
+}
+
+fn run(
+    solution: &Solution,
+    path: Option<PathBuf>,
+    answers: Option<&Answers>,
+    strict: bool,
+    explain: bool,
+) -> Report {
+    let mut report = Report {
+        day: solution.day,
+        input: path.clone(),
+        result: Err(RunError::Panic(String::new())),
+        check: None,
+        explanation: None,
+        parse: Duration::ZERO,
+        solve: Duration::ZERO,
+    };
+
+    // Reading the input isn't timed, so parse is the same phase aoc time measures
+    let lines = match read_input(path.as_ref()) {
+        Ok(lines) => lines,
+        Err(err) => {
+            report.result = Err(err);
+            return report;
+        }
+    };
+    let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    // The panic message ends up in the report, keep the default hook quiet
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(|| {
+        // Checking parses every line a second time, so it's left out of the timings
+        let checked = check_lines(solution, &input, strict);
+        let start = Instant::now();
+        let parsed = checked.and_then(|()| (solution.parse)(&input));
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = parsed.and_then(|parsed| parsed.solve());
+        (answer, parse, start.elapsed())
+    });
+
+    report.result = match result {
+        Ok((answer, parse, solve)) => {
+            (report.parse, report.solve) = (parse, solve);
+            answer.map_err(RunError::Solve)
+        }
+        Err(payload) => Err(RunError::Panic(panic_message(payload))),
+    };
+
+    // Explaining isn't part of the timings, and failing to doesn't change the answer
+    let explain = solution.explain.filter(|_| explain);
+    if let (Some(explain), Ok(_)) = (explain, &report.result) {
+        report.explanation = Some(match panic::catch_unwind(|| explain(&input)) {
+            Ok(explanation) => explanation.map_err(RunError::Solve),
+            Err(payload) => Err(RunError::Panic(panic_message(payload))),
+        });
+    }
+    panic::set_hook(hook);
+
+    if let (Some(answers), Ok(answer)) = (answers, &report.result) {
+        report.check = Some(answers.check(report.day, answer));
+    }
+    report
+}
+
+// Integers that fit are JSON numbers, everything else is a string
+fn answer_json(answer: &Answer) -> Value {
+    answer
+        .to_i128()
+        .and_then(|num| {
+            i64::try_from(num)
+                .map(Value::from)
+                .or_else(|_| u64::try_from(num).map(Value::from))
+                .ok()
+        })
+        .unwrap_or_else(|| Value::String(answer.to_string()))
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    // read, parse, input or panic
+    kind: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nom_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    // context() names inside the day's parser, outermost first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stack: Vec<&'static str>,
 }
 
-fn main() {
-    let cli = Cli::parse();
-    dispatch(cli.name.as_str());
+impl From<&RunError> for JsonError {
+    fn from(err: &RunError) -> Self {
+        let mut json = Self {
+            kind: "panic",
+            message: err.to_string(),
+            line: None,
+            column: None,
+            context: None,
+            nom_kind: None,
+            expected: None,
+            stack: vec![],
+        };
+
+        match err {
+            RunError::Read(_) => json.kind = "read",
+            RunError::Panic(_) => {}
+            RunError::Solve(AocError::Parse(err)) => {
+                json.kind = "parse";
+                json.line = Some(err.line);
+                json.column = Some(err.column);
+                json.context = Some(err.context);
+                json.nom_kind = Some(format!("{:?}", err.kind));
+                json.expected = Some(err.expected());
+                json.stack = err.stack.clone();
+            }
+            RunError::Solve(AocError::Input { context, .. }) => {
+                json.kind = "input";
+                json.context = Some(context);
+            }
+        }
+        json
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: Option<Value>,
+    parse_ns: u64,
+    solve_ns: u64,
+    // - for stdin
+    input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    check: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation_error: Option<JsonError>,
+    error: Option<JsonError>,
+}
+
+impl From<&Report> for JsonReport {
+    fn from(report: &Report) -> Self {
+        let (check, expected) = match &report.check {
+            None => (None, None),
+            Some(Check::Correct) => (Some("correct"), None),
+            Some(Check::Wrong(expected)) => (Some("wrong"), Some(answer_json(expected))),
+            Some(Check::Unknown) => (Some("unknown"), None),
+        };
+
+        Self {
+            year: report.day.year,
+            day: report.day.number,
+            part: if report.day.part_two { 2 } else { 1 },
+            answer: report.result.as_ref().ok().map(answer_json),
+            parse_ns: report.parse.as_nanos() as u64,
+            solve_ns: report.solve.as_nanos() as u64,
+            input: report
+                .input
+                .as_ref()
+                .map_or_else(|| "-".to_owned(), |path| path.display().to_string()),
+            check,
+            expected,
+            explanation: report
+                .explanation
+                .as_ref()
+                .and_then(|explanation| explanation.as_ref().ok().cloned()),
+            explanation_error: report
+                .explanation
+                .as_ref()
+                .and_then(|explanation| explanation.as_ref().err().map(JsonError::from)),
+            error: report.result.as_ref().err().map(JsonError::from),
+        }
+    }
+}
+
+fn print_json(value: &impl Serialize) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("reports always serialize")
+    );
+}
+
+fn check_text(check: &Check) -> String {
+    match check {
+        Check::Correct => "ok".to_owned(),
+        Check::Wrong(expected) => format!("WRONG, expected {}", expected),
+        Check::Unknown => "unknown".to_owned(),
+    }
+}
+
+fn print_table(reports: &[Report], checked: bool) {
+    print!(
+        "{:<4} {:<4} {:<4} {:<20} {:>12} {:>12}",
+        "year", "day", "part", "answer", "parse", "solve"
+    );
+    if checked {
+        print!("  check");
+    }
+    println!();
+
+    reports.iter().for_each(|report| {
+        let answer = match &report.result {
+            Ok(answer) => answer.to_string(),
+            Err(err) => format!("FAILED: {}", err),
+        };
+        print!(
+            "{:<4} {:<4} {:<4} {:<20} {:>12.2?} {:>12.2?}",
+            report.day.year,
+            report.day.number,
+            if report.day.part_two { 2 } else { 1 },
+            answer,
+            report.parse,
+            report.solve,
+        );
+        if let Some(check) = &report.check {
+            print!("  {}", check_text(check));
+        }
+        println!();
+    });
+}
+
+fn run_many(
+    days: impl Fn(Day) -> bool,
+    inputs: &InputArgs,
+    answers: Option<Answers>,
+    format: Format,
+) -> ExitCode {
+    let reports: Vec<_> = advent2023::solutions()
+        .into_iter()
+        .filter(|solution| days(solution.day))
+        .map(|solution| {
+            let path = Some(inputs.path(solution.day));
+            run(solution, path, answers.as_ref(), inputs.strict, false)
+        })
+        .collect();
+
+    match format {
+        Format::Text => print_table(&reports, answers.is_some()),
+        Format::Json => print_json(&reports.iter().map(JsonReport::from).collect::<Vec<_>>()),
+    }
+    if reports.iter().all(|report| !report.failed()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// Parse errors also show the line that failed
+fn print_failure(day: Day, err: &RunError) {
+    eprintln!("{} failed: {}", day, err);
+    if let RunError::Solve(AocError::Parse(err)) = err {
+        eprintln!("{}", err.snippet());
+    }
+}
+
+fn run_one(args: RunArgs, year: Option<u16>, format: Format) -> ExitCode {
+    let day = args
+        .name
+        .expect("name is required without a subcommand")
+        .day(year);
+    let Some(solution) = advent2023::solution(day) else {
+        eprintln!("No solution registered for {}", day);
+        return ExitCode::FAILURE;
+    };
+
+    if args.explain && solution.explain.is_none() {
+        eprintln!("{} has nothing to explain", day);
+        return ExitCode::FAILURE;
+    }
+
+    let answers = match args.check.load(&args.inputs) {
+        Ok(answers) => answers,
+        Err(code) => return code,
+    };
+    let path = match args.input {
+        Some(path) if path.as_os_str() == "-" => None,
+        Some(path) => Some(path),
+        None => Some(args.inputs.path(day)),
+    };
+
+    let report = run(
+        solution,
+        path,
+        answers.as_ref(),
+        args.inputs.strict,
+        args.explain,
+    );
+    match (format, &report.result, &report.check) {
+        (Format::Json, _, _) => print_json(&JsonReport::from(&report)),
+        (Format::Text, Ok(answer), None) => println!("Result is {}", answer),
+        (Format::Text, Ok(answer), Some(check)) => {
+            println!("Result is {} ({})", answer, check_text(check))
+        }
+        (Format::Text, Err(err), _) => print_failure(day, err),
+    }
+    match (format, &report.explanation) {
+        (Format::Text, Some(Ok(explanation))) => println!("{}", explanation),
+        (Format::Text, Some(Err(err))) => eprintln!("warning: could not explain {}: {}", day, err),
+        _ => {}
+    }
+
+    if report.failed() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[derive(Serialize)]
+struct JsonStats {
+    runs: usize,
+    min_ns: u64,
+    median_ns: u64,
+    p95_ns: u64,
+    mean_ns: u64,
+}
+
+impl From<&Stats> for JsonStats {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            runs: stats.runs,
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            p95_ns: stats.p95.as_nanos() as u64,
+            mean_ns: stats.mean.as_nanos() as u64,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonTiming {
+    year: u16,
+    day: u8,
+    part: u8,
+    input: String,
+    parse: JsonStats,
+    solve: JsonStats,
+    total: JsonStats,
+}
+
+fn time_one(day: Day, path: PathBuf, runs: usize, strict: bool, format: Format) -> ExitCode {
+    let Some(solution) = advent2023::solution(day) else {
+        eprintln!("No solution registered for {}", day);
+        return ExitCode::FAILURE;
+    };
+
+    let lines = match read_input(Some(&path)) {
+        Ok(lines) => lines,
+        Err(err) => {
+            print_failure(day, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+    // An untimed run up front, which also makes sure the day succeeds at all
+    if let Err(err) = check_lines(solution, &input, strict).and_then(|()| (solution.run)(&input)) {
+        print_failure(day, &RunError::Solve(err));
+        return ExitCode::FAILURE;
+    }
+
+    let parse = timing::time(runs, || (solution.parse)(&input));
+    let solve = timing::time_batched(
+        runs,
+        || (solution.parse)(&input).expect("parsed once already"),
+        |parsed| parsed.solve(),
+    );
+    let total = timing::time(runs, || (solution.run)(&input));
+    let (Some(parse), Some(solve), Some(total)) = (parse, solve, total) else {
+        eprintln!("Need at least one run to time");
+        return ExitCode::FAILURE;
+    };
+
+    match format {
+        Format::Json => print_json(&JsonTiming {
+            year: day.year,
+            day: day.number,
+            part: if day.part_two { 2 } else { 1 },
+            input: path.display().to_string(),
+            parse: JsonStats::from(&parse),
+            solve: JsonStats::from(&solve),
+            total: JsonStats::from(&total),
+        }),
+        Format::Text => {
+            println!("{}, {} runs on {}", day, total.runs, path.display());
+            println!(
+                "{:<6} {:>12} {:>12} {:>12} {:>12}",
+                "", "min", "median", "p95", "mean"
+            );
+            [("parse", parse), ("solve", solve), ("total", total)]
+                .into_iter()
+                .for_each(|(phase, stats)| {
+                    println!(
+                        "{:<6} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+                        phase, stats.min, stats.median, stats.p95, stats.mean
+                    )
+                });
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[derive(Serialize)]
+struct JsonQuery {
+    from: String,
+    to: String,
+    // Every category on the way, from and to included
+    path: Vec<String>,
+    results: Vec<JsonQueryResult>,
+}
+
+#[derive(Serialize)]
+struct JsonQueryResult {
+    input: String,
+    // A number for numbers, [start, end) pairs for ranges
+    output: Value,
+}
+
+fn query_one(
+    day: Day,
+    path: PathBuf,
+    (from, to): (&str, &str),
+    values: &[QueryValue],
+    format: Format,
+) -> ExitCode {
+    if (day.year, day.number) != (DEFAULT_YEAR, 5) {
+        eprintln!(
+            "{} has no categories to query, only {} day 5 does",
+            day, DEFAULT_YEAR
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let lines = match read_input(Some(&path)) {
+        Ok(lines) => lines,
+        Err(err) => {
+            print_failure(day, &err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let almanac = match parser::day5::parse_input(&input) {
+        Ok(almanac) => almanac,
+        Err(err) => {
+            print_failure(day, &RunError::Solve(err));
+            return ExitCode::FAILURE;
+        }
+    };
+    let maps = match almanac.path(from, to) {
+        Ok(maps) => maps,
+        Err(err) => {
+            print_failure(day, &RunError::Solve(err));
+            return ExitCode::FAILURE;
+        }
+    };
+    let categories: Vec<_> = [from]
+        .into_iter()
+        .chain(maps.iter().map(|map| map.to.as_str()))
+        .map(str::to_owned)
+        .collect();
+    let map = Almanac::compose(&maps);
+
+    let results = values.iter().map(|value| match value {
+        QueryValue::Number(num) => {
+            let num = map.get(*num);
+            (value, Value::from(num), num.to_string())
+        }
+        QueryValue::Range(range) => {
+            let ranges = map.apply(slice::from_ref(range));
+            let json = ranges
+                .iter()
+                .map(|range| Value::from(vec![range.start, range.end]))
+                .collect();
+            let text = ranges
+                .iter()
+                .map(|range| format!("{:?}", range))
+                .collect::<Vec<_>>()
+                .join(" ");
+            (value, Value::Array(json), text)
+        }
+    });
+
+    match format {
+        Format::Json => print_json(&JsonQuery {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            path: categories,
+            results: results
+                .map(|(input, output, _)| JsonQueryResult {
+                    input: input.to_string(),
+                    output,
+                })
+                .collect(),
+        }),
+        Format::Text => {
+            println!("{}", categories.join(" -> "));
+            results
+                .for_each(|(input, _, output)| println!("{} {} -> {} {}", from, input, to, output));
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// aoc YYYY ... is the same as aoc ... --year YYYY, which goes last since
+// flags before a subcommand are taken as running a day
+fn year_shorthand(mut args: Vec<OsString>) -> Vec<OsString> {
+    if let Some(year) = args
+        .get(1)
+        .and_then(|arg| arg.to_str())
+        .and_then(parse_year)
+    {
+        args.remove(1);
+        args.push(format!("--year={}", year).into());
+    }
+    args
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse_from(year_shorthand(env::args_os().collect()));
+    let year = cli.year;
+    let in_year = move |day: Day| year.is_none_or(|year| day.year == year);
+
+    match cli.command {
+        None => run_one(cli.run, year, cli.format),
+        Some(Command::All { inputs, check }) => match check.load(&inputs) {
+            Ok(answers) => run_many(in_year, &inputs, answers, cli.format),
+            Err(code) => code,
+        },
+        Some(Command::Range {
+            days,
+            inputs,
+            check,
+        }) => match check.load(&inputs) {
+            Ok(answers) => run_many(
+                |day| day.year == year.unwrap_or(DEFAULT_YEAR) && days.contains(&day.number),
+                &inputs,
+                answers,
+                cli.format,
+            ),
+            Err(code) => code,
+        },
+        Some(Command::Check { inputs, answers }) => {
+            let check = CheckArgs {
+                check: true,
+                answers,
+            };
+            match check.load(&inputs) {
+                Ok(answers) => run_many(in_year, &inputs, answers, cli.format),
+                Err(code) => code,
+            }
+        }
+        Some(Command::Time {
+            name,
+            input,
+            runs,
+            inputs,
+        }) => {
+            let day = name.day(year);
+            let path = input.unwrap_or_else(|| inputs.path(day));
+            time_one(day, path, runs, inputs.strict, cli.format)
+        }
+        Some(Command::Query {
+            name,
+            values,
+            from,
+            to,
+            input,
+            inputs,
+        }) => {
+            let day = name.day(year);
+            let path = input.unwrap_or_else(|| inputs.path(day));
+            query_one(day, path, (&from, &to), &values, cli.format)
+        }
+        Some(Command::List) => {
+            advent2023::solutions()
+                .into_iter()
+                .filter(|solution| in_year(solution.day))
+                .for_each(|solution| println!("{}", solution.day.name()));
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{year_shorthand, QueryValue, RunError};
+    use advent2023::{inputs, AocError, Day, Solution};
+    use std::{env, ffi::OsString, fs, ops::RangeInclusive};
+    use test_case::test_case;
+
+    fn cannot_explain(_: &[&str]) -> Result<String, AocError> {
+        Err(AocError::input("test", "nothing to say"))
+    }
+
+    #[test]
+    fn explain_fails() {
+        let day = Day::new(5, true);
+        let real = advent2023::solution(day).unwrap();
+        let solution = Solution {
+            day,
+            run: real.run,
+            parse: real.parse,
+            check_lines: None,
+            explain: Some(cannot_explain),
+            examples: &[],
+        };
+        let path = inputs::example_path(inputs::EXAMPLE_DIR, day);
+
+        // The answer stands, only the explanation failed
+        let report = super::run(&solution, Some(path), None, false, true);
+        assert!(!report.failed());
+        assert!(matches!(report.result, Ok(answer) if answer == 46));
+        match report.explanation {
+            Some(Err(RunError::Solve(err))) => assert_eq!("test: nothing to say", err.to_string()),
+            _ => panic!("expected the explanation to fail"),
+        }
+    }
+
+    // Day 2 skips games it can't read unless the run is strict
+    #[test_case(false => "4" ; "when skipping")]
+    #[test_case(true => "line 2, column 11: could not parse day2 > color, expected a keyword" ; "when strict")]
+    fn strict(strict: bool) -> String {
+        let path =
+            env::temp_dir().join(format!("aoc_strict_{}_{}.dat", std::process::id(), strict));
+        fs::write(&path, "Game 1: 3 blue\nGame 2: 4 rad\nGame 3: 2 red\n").unwrap();
+        let solution = advent2023::solution(Day::new(2, false)).unwrap();
+        let report = super::run(solution, Some(path.clone()), None, strict, false);
+        fs::remove_file(path).unwrap();
+        match report.result {
+            Ok(answer) => answer.to_string(),
+            Err(RunError::Solve(err)) => err.to_string(),
+            Err(_) => panic!("expected an answer or a parse error"),
+        }
+    }
+
+    #[test_case("1..=6" => Ok(1..=6) ; "when inclusive")]
+    #[test_case("1..7" => Ok(1..=6) ; "when exclusive")]
+    #[test_case("3.." => Ok(3..=25) ; "when open end")]
+    #[test_case("..=4" => Ok(1..=4) ; "when open start")]
+    #[test_case("5" => Ok(5..=5) ; "when single day")]
+    #[test_case("" => Err(String::from(r#"invalid day number """#)) ; "when empty")]
+    #[test_case("0" => Err(String::from("days start at 1")) ; "when zero")]
+    #[test_case("0..=3" => Err(String::from("days start at 1")) ; "when zero start")]
+    #[test_case("..1" => Err(String::from(r#""..1" has no days in it"#)) ; "when nothing before day 1")]
+    #[test_case("6..=2" => Err(String::from(r#""6..=2" has no days in it"#)) ; "when backwards")]
+    fn parse_range(s: &str) -> Result<RangeInclusive<u8>, String> {
+        super::parse_range(s)
+    }
+
+    #[test_case(&["aoc", "2023", "5p2"] => vec!["aoc", "5p2", "--year=2023"] ; "when year first")]
+    #[test_case(&["aoc", "2023", "time", "5"] => vec!["aoc", "time", "5", "--year=2023"] ; "when subcommand")]
+    #[test_case(&["aoc", "5p2"] => vec!["aoc", "5p2"] ; "when no year")]
+    #[test_case(&["aoc", "2023:5p2"] => vec!["aoc", "2023:5p2"] ; "when year in day")]
+    #[test_case(&["aoc", "time", "2023"] => vec!["aoc", "time", "2023"] ; "when year later")]
+    fn shorthand(args: &[&str]) -> Vec<String> {
+        year_shorthand(args.iter().map(OsString::from).collect())
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test_case("81" => Ok(QueryValue::Number(81)) ; "when number")]
+    #[test_case("-3" => Ok(QueryValue::Number(-3)) ; "when negative")]
+    #[test_case("79..93" => Ok(QueryValue::Range(79..93)) ; "when range")]
+    #[test_case("93..79" => Err(String::from(r#""93..79" has no numbers in it"#)) ; "when backwards")]
+    #[test_case("79..79" => Err(String::from(r#""79..79" has no numbers in it"#)) ; "when empty")]
+    #[test_case("79..=93" => Err(String::from(r#"expected a number or range like 79..93, not "79..=93""#)) ; "when inclusive")]
+    #[test_case("x" => Err(String::from(r#"expected a number or range like 79..93, not "x""#)) ; "when not a number")]
+    fn query_value(s: &str) -> Result<QueryValue, String> {
+        s.parse()
+    }
 }
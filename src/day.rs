@@ -0,0 +1,173 @@
+use std::{fmt, str::FromStr};
+
+// Every day without an explicit year is in this one
+pub const DEFAULT_YEAR: u16 = 2023;
+
+// Day specifier: (YYYY:)?[12]?[0-9](p2)?, mirrors advent2023_macros' Day
+// Ordered by year, then day, then part
+#[derive(PartialEq, PartialOrd, Eq, Ord, Copy, Clone, Debug, Hash)]
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    pub part_two: bool,
+}
+
+impl Day {
+    pub const fn new(number: u8, part_two: bool) -> Self {
+        Self::in_year(DEFAULT_YEAR, number, part_two)
+    }
+
+    pub const fn in_year(year: u16, number: u8, part_two: bool) -> Self {
+        Self {
+            year,
+            number,
+            part_two,
+        }
+    }
+
+    // The year, unless it is the default, as a prefix like 2024:
+    fn year_prefix(self, separator: &str) -> String {
+        if self.year == DEFAULT_YEAR {
+            String::new()
+        } else {
+            format!("{}{}", self.year, separator)
+        }
+    }
+
+    // The day as written on the command line, e.g. 5p2 or 2024:5p2
+    pub fn name(self) -> String {
+        let prefix = self.year_prefix(":");
+        if self.part_two {
+            format!("{}{}p2", prefix, self.number)
+        } else {
+            format!("{}{}", prefix, self.number)
+        }
+    }
+
+    // Criterion benchmark ID, e.g. day 5 p2 or 2024 day 5 p2
+    pub fn bench_name(self) -> String {
+        let prefix = self.year_prefix(" ");
+        if self.part_two {
+            format!("{}day {} p2", prefix, self.number)
+        } else {
+            format!("{}day {}", prefix, self.number)
+        }
+    }
+
+    // Parse a day specifier, which is in year unless it names its own
+    pub fn parse_in_year(s: &str, year: u16) -> Result<Self, ParseDayError> {
+        let err = || ParseDayError(s.to_owned());
+
+        let (year, day) = match s.split_once(':') {
+            Some((year, day)) => (parse_year(year).ok_or_else(err)?, day),
+            None => (year, s),
+        };
+
+        let trimmed = day.strip_prefix("day").unwrap_or(day);
+        let (number, part_two) = match trimmed.strip_suffix("p2") {
+            Some(number) => (number, true),
+            None => (trimmed, false),
+        };
+
+        if number.is_empty() || number.starts_with('0') {
+            return Err(err());
+        }
+        let number: u8 = number.parse().map_err(|_| err())?;
+        if number > 25 {
+            return Err(err());
+        }
+
+        Ok(Self::in_year(year, number, part_two))
+    }
+}
+
+// Advent of Code started in 2015
+pub fn parse_year(s: &str) -> Option<u16> {
+    if s.len() != 4 {
+        return None;
+    }
+    s.parse().ok().filter(|year| *year >= 2015)
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.part_two {
+            write!(f, "{}day{}p2", self.year_prefix(" "), self.number)
+        } else {
+            write!(f, "{}day{}", self.year_prefix(" "), self.number)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseDayError(String);
+
+impl fmt::Display for ParseDayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid day specifier {:?}, expected e.g. 5, 5p2 or 2023:5p2",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseDayError {}
+
+impl FromStr for Day {
+    type Err = ParseDayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_in_year(s, DEFAULT_YEAR)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Day;
+    use test_case::test_case;
+
+    #[test_case("1" => Day::new(1, false))]
+    #[test_case("1p2" => Day::new(1, true))]
+    #[test_case("25" => Day::new(25, false))]
+    #[test_case("day5p2" => Day::new(5, true))]
+    #[test_case("2023:5p2" => Day::new(5, true) ; "when default year")]
+    #[test_case("2015:day1" => Day::in_year(2015, 1, false) ; "when other year")]
+    fn parse(input: &str) -> Day {
+        input.parse().unwrap()
+    }
+
+    #[test_case("" ; "when empty")]
+    #[test_case("0" ; "when zero")]
+    #[test_case("26" ; "when past christmas")]
+    #[test_case("05" ; "when leading zero")]
+    #[test_case("p2" ; "when no number")]
+    #[test_case("5p3" ; "when wrong part")]
+    #[test_case("2014:5" ; "when before advent of code")]
+    #[test_case("23:5" ; "when short year")]
+    #[test_case("2023:" ; "when no day")]
+    #[should_panic]
+    fn parse_panics(input: &str) {
+        input.parse::<Day>().unwrap();
+    }
+
+    #[test_case("5p2", 2024 => Day::in_year(2024, 5, true) ; "when bare")]
+    #[test_case("2023:5p2", 2024 => Day::new(5, true) ; "when explicit year")]
+    fn parse_in_year(input: &str, year: u16) -> Day {
+        Day::parse_in_year(input, year).unwrap()
+    }
+
+    #[test_case(Day::new(5, false) => "day5")]
+    #[test_case(Day::new(5, true) => "day5p2")]
+    #[test_case(Day::in_year(2024, 5, true) => "2024 day5p2")]
+    fn display(day: Day) -> String {
+        day.to_string()
+    }
+
+    #[test_case(Day::new(5, true) => ("5p2".to_owned(), "day 5 p2".to_owned()))]
+    #[test_case(Day::in_year(2024, 5, false) => ("2024:5".to_owned(), "2024 day 5".to_owned()))]
+    fn names(day: Day) -> (String, String) {
+        assert_eq!(Ok(day), day.name().parse());
+        (day.name(), day.bench_name())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::parser;
+use nom::error::ErrorKind;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AocError {
+    // A line of the input did not match what the day's parser expected
+    Parse(ParseError),
+    // The input parsed, but is not shaped the way the puzzle needs
+    Input {
+        context: &'static str,
+        message: String,
+    },
+}
+
+// Where on a line parsing stopped, and what the parser wanted there
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    // 1-indexed
+    pub line: usize,
+    // 1-indexed, in chars
+    pub column: usize,
+    // Names the parser that failed
+    pub context: &'static str,
+    pub kind: ErrorKind,
+    pub expected: Option<char>,
+    // The context() names parsing was inside, outermost first
+    pub stack: Vec<&'static str>,
+    // The line that failed, for the snippet
+    pub source: String,
+}
+
+impl ParseError {
+    // What was expected at the column, as it reads in a message
+    pub fn expected(&self) -> String {
+        if let Some(ch) = self.expected {
+            return format!("{:?}", ch);
+        }
+
+        match self.kind {
+            ErrorKind::Digit => "a number",
+            ErrorKind::Space | ErrorKind::MultiSpace => "a space",
+            ErrorKind::Alpha => "a word",
+            ErrorKind::Tag => "a keyword",
+            ErrorKind::OneOf | ErrorKind::NoneOf => "a different character",
+            ErrorKind::Eof => "the end of the line",
+            ErrorKind::TooLarge => "a smaller number",
+            kind => return kind.description().to_lowercase(),
+        }
+        .to_owned()
+    }
+
+    // The line with a caret under the column, e.g.
+    // 2 | Distance: x
+    //   |           ^ expected a number
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string();
+        // Keep tabs so the caret lines up however they are shown
+        let indent: String = self
+            .source
+            .chars()
+            .take(self.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "{} | {}\n{} | {}^ expected {}",
+            gutter,
+            self.source,
+            " ".repeat(gutter.len()),
+            indent,
+            self.expected()
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: could not parse {}",
+            self.line, self.column, self.context
+        )?;
+        self.stack
+            .iter()
+            .try_for_each(|context| write!(f, " > {}", context))?;
+        write!(f, ", expected {}", self.expected())
+    }
+}
+
+impl AocError {
+    // Wrap a nom error from parsing line, at 0-based index idx
+    pub fn parse(
+        idx: usize,
+        line: &str,
+        context: &'static str,
+        err: nom::Err<parser::Error>,
+    ) -> Self {
+        let err = match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => err,
+            nom::Err::Incomplete(_) => parser::Error::new("", ErrorKind::Complete),
+        };
+
+        // The error's input is the rest of the line from where parsing stopped
+        let offset = line.len().saturating_sub(err.input.len());
+        let column = line.get(..offset).map_or(0, |done| done.chars().count()) + 1;
+
+        Self::Parse(ParseError {
+            line: idx + 1,
+            column,
+            context,
+            kind: err.kind,
+            expected: err.expected,
+            stack: err.contexts.into_iter().rev().collect(),
+            source: line.to_owned(),
+        })
+    }
+
+    pub fn input(context: &'static str, message: impl Into<String>) -> Self {
+        Self::Input {
+            context,
+            message: message.into(),
+        }
+    }
+
+    // Move a parse error from a section of the input to its place in the whole input
+    pub fn at_offset(self, offset: usize) -> Self {
+        match self {
+            Self::Parse(mut err) => {
+                err.line += offset;
+                Self::Parse(err)
+            }
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::Input { context, message } => write!(f, "{}: {}", context, message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+#[cfg(test)]
+mod test {
+    use super::AocError;
+    use crate::parser::{self, Error};
+    use nom::error::ErrorKind;
+    use test_case::test_case;
+
+    fn parse_error(line: &str, rest: &str, kind: ErrorKind) -> AocError {
+        AocError::parse(
+            2,
+            line,
+            "day6 times",
+            nom::Err::Error(Error::new(rest, kind)),
+        )
+    }
+
+    #[test_case(parse_error("Time: x", "x", ErrorKind::Digit)
+        => "line 3, column 7: could not parse day6 times, expected a number" ; "parse")]
+    #[test_case(parse_error("é x", "x", ErrorKind::Digit)
+        => "line 3, column 3: could not parse day6 times, expected a number" ; "when non ascii")]
+    #[test_case(parse_error("Time: 7 x", "x", ErrorKind::Eof)
+        => "line 3, column 9: could not parse day6 times, expected the end of the line" ; "when trailing")]
+    #[test_case(AocError::input("day5", "missing seeds") => "day5: missing seeds" ; "input")]
+    fn display(err: AocError) -> String {
+        err.to_string()
+    }
+
+    #[test_case("Game 1: 3 blue, 4 rad" => "line 1, column 19: could not parse day2 > color, expected a keyword" ; "when context")]
+    #[test_case("Game 1: 3 blue 4 red" => "line 1, column 15: could not parse day2, expected the end of the line" ; "when trailing")]
+    #[test_case("Game x: 3 blue" => "line 1, column 6: could not parse day2 > game id, expected a number" ; "when nested")]
+    fn display_stack(line: &str) -> String {
+        parser::check_lines(&[line], "day2", parser::day2::parse_line)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test_case("Distance: x", 11 => "2 | Distance: x\n  |           ^ expected a number" ; "when space")]
+    #[test_case("\tx", 2 => "2 | \tx\n  | \t^ expected a number" ; "when tab")]
+    fn snippet(line: &str, column: usize) -> String {
+        let rest = &line[line.char_indices().nth(column - 1).unwrap().0..];
+        match AocError::parse(
+            1,
+            line,
+            "day6",
+            nom::Err::Error(Error::new(rest, ErrorKind::Digit)),
+        ) {
+            AocError::Parse(err) => err.snippet(),
+            err => panic!("{:?}", err),
+        }
+    }
+}
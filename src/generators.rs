@@ -0,0 +1,313 @@
+// Seeded random puzzle inputs in the same format as the real ones, for
+// benchmarking without committing anybody's personal inputs
+// size is the number of lines, or of ranges per map for day 5
+use crate::Day;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+pub const DEFAULT_SEED: u64 = 2023;
+
+type Generator = fn(&mut StdRng, usize) -> Vec<String>;
+
+// Both parts of a day share an input, these are all for 2023
+fn generator(day: Day) -> Option<Generator> {
+    match (day.year, day.number) {
+        (2023, 1) => Some(day1),
+        (2023, 2) => Some(day2),
+        (2023, 3) => Some(day3),
+        (2023, 4) => Some(day4),
+        (2023, 5) => Some(day5),
+        (2023, 6) => Some(day6),
+        (2023, 7) => Some(day7),
+        _ => None,
+    }
+}
+
+// The same day, size and seed always give the same input
+pub fn generate(day: Day, size: usize, seed: u64) -> Option<Vec<String>> {
+    let generator = generator(day)?;
+    let mut rng = StdRng::seed_from_u64(seed ^ u64::from(day.number));
+    Some(generator(&mut rng, size))
+}
+
+// Inputs small enough to solve by brute force, for checking against reference solutions
+// Day 5 numbers stay below 100 and day 6 has two short races
+pub fn generate_small(day: Day, seed: u64) -> Option<Vec<String>> {
+    let generator = generator(day)?;
+    let mut rng = StdRng::seed_from_u64(seed ^ u64::from(day.number));
+    Some(match day.number {
+        5 => almanac(&mut rng, 4, 100),
+        6 => races(&mut rng, 2),
+        _ => generator(&mut rng, 10),
+    })
+}
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Calibration lines, letters and spelled out digits around at least one digit
+pub fn day1(rng: &mut StdRng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let mut line: Vec<String> = (0..rng.gen_range(1..8))
+                .map(|_| match rng.gen_range(0..4) {
+                    0 => rng.gen_range(1..=9).to_string(),
+                    1 => DIGIT_NAMES.choose(rng).unwrap().to_string(),
+                    _ => (0..rng.gen_range(1..5))
+                        .map(|_| rng.gen_range('a'..='z'))
+                        .collect(),
+                })
+                .collect();
+            let idx = rng.gen_range(0..=line.len());
+            line.insert(idx, rng.gen_range(1..=9).to_string());
+            line.concat()
+        })
+        .collect()
+}
+
+// Games of up to six rounds, each showing some of the three colours
+pub fn day2(rng: &mut StdRng, size: usize) -> Vec<String> {
+    (1..=size)
+        .map(|id| {
+            let rounds: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    let count = rng.gen_range(1..=colors.len());
+                    colors[..count]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {}: {}", id, rounds.join("; "))
+        })
+        .collect()
+}
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+// A 140 wide schematic of numbers and symbols on a background of periods
+pub fn day3(rng: &mut StdRng, size: usize) -> Vec<String> {
+    const WIDTH: usize = 140;
+    (0..size)
+        .map(|_| {
+            let mut line = String::with_capacity(WIDTH);
+            while line.len() < WIDTH {
+                match rng.gen_range(0..10) {
+                    0 => line.push(*SYMBOLS.choose(rng).unwrap() as char),
+                    1 | 2 => {
+                        let number = rng.gen_range(1..1000).to_string();
+                        if line.len() + number.len() < WIDTH {
+                            // Separate from the next number
+                            line.push_str(&number);
+                            line.push('.');
+                        } else {
+                            line.push('.');
+                        }
+                    }
+                    _ => line.push('.'),
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+// Scratchcards with 10 winning numbers and 25 numbers, winning is rare and
+// never past the last card so the day 4 part 2 copies stay small
+pub fn day4(rng: &mut StdRng, size: usize) -> Vec<String> {
+    let format_numbers = |numbers: &[i64]| {
+        numbers
+            .iter()
+            .map(|num| format!("{:>2}", num))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    (1..=size)
+        .map(|id| {
+            let mut numbers: Vec<i64> = (1..100).collect();
+            numbers.shuffle(rng);
+            let matches = if rng.gen_bool(0.8) {
+                0
+            } else {
+                rng.gen_range(1..=4).min(size - id)
+            };
+
+            let winners = &numbers[..10];
+            let mut card: Vec<i64> = winners[..matches]
+                .iter()
+                .chain(&numbers[10..35 - matches])
+                .copied()
+                .collect();
+            card.shuffle(rng);
+
+            format!(
+                "Card {:>3}: {} | {}",
+                id,
+                format_numbers(winners),
+                format_numbers(&card)
+            )
+        })
+        .collect()
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// An almanac of 10 seed ranges and 7 maps, each shuffling a partition of the
+// numbers below 2^32 with a few pieces left unmapped
+pub fn day5(rng: &mut StdRng, size: usize) -> Vec<String> {
+    almanac(rng, size, 1 << 32)
+}
+
+// An almanac with every number below limit, which must be at least 20
+fn almanac(rng: &mut StdRng, size: usize, limit: i64) -> Vec<String> {
+    let size = size.max(1);
+
+    let seeds: Vec<String> = (0..10)
+        .flat_map(|_| {
+            let start = rng.gen_range(0..limit / 2);
+            [start, rng.gen_range(1..limit / 20)]
+        })
+        .map(|num| num.to_string())
+        .collect();
+    let mut lines = vec![format!("seeds: {}", seeds.join(" ")), String::new()];
+
+    CATEGORIES.windows(2).for_each(|names| {
+        lines.push(format!("{}-to-{} map:", names[0], names[1]));
+
+        let mut cuts: Vec<i64> = (1..size).map(|_| rng.gen_range(1..limit)).collect();
+        cuts.extend([0, limit]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut pieces: Vec<(i64, i64)> = cuts
+            .windows(2)
+            .map(|cut| (cut[0], cut[1] - cut[0]))
+            .collect();
+
+        let mut shuffled = pieces.clone();
+        shuffled.shuffle(rng);
+        let mut dest = 0;
+        let mut dests: Vec<(i64, i64)> = shuffled
+            .into_iter()
+            .map(|(src, len)| {
+                dest += len;
+                (src, dest - len)
+            })
+            .collect();
+        dests.sort_unstable();
+
+        pieces.shuffle(rng);
+        pieces
+            .into_iter()
+            .filter(|_| rng.gen_bool(0.9))
+            .for_each(|(src, len)| {
+                let idx = dests.binary_search_by_key(&src, |&(src, _)| src).unwrap();
+                lines.push(format!("{} {} {}", dests[idx].1, src, len));
+            });
+        lines.push(String::new());
+    });
+    lines.pop();
+    lines
+}
+
+// Four races that can each be won, size is ignored since part 2 joins the numbers
+pub fn day6(rng: &mut StdRng, _size: usize) -> Vec<String> {
+    races(rng, 4)
+}
+
+fn races(rng: &mut StdRng, count: usize) -> Vec<String> {
+    let (times, records): (Vec<i64>, Vec<i64>) = (0..count)
+        .map(|_| {
+            let time = rng.gen_range(7..100);
+            let best = (time / 2) * (time - time / 2);
+            (time, rng.gen_range(time..best))
+        })
+        .unzip();
+
+    let format_row = |title: &str, nums: &[i64]| {
+        let nums: String = nums.iter().map(|num| format!("{:>6}", num)).collect();
+        format!("{:<9}{}", title, nums)
+    };
+    vec![
+        format_row("Time:", &times),
+        format_row("Distance:", &records),
+    ]
+}
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+// Hands of five cards and their bids
+pub fn day7(rng: &mut StdRng, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let hand: String = (0..5)
+                .map(|_| *CARDS.choose(rng).unwrap() as char)
+                .collect();
+            format!("{} {}", hand, rng.gen_range(1..=1000))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, generate_small, DEFAULT_SEED};
+    use crate::{solutions, Day};
+    use test_case::test_case;
+
+    #[test]
+    fn deterministic() {
+        let day = Day::new(5, false);
+        assert_eq!(
+            generate(day, 10, DEFAULT_SEED),
+            generate(day, 10, DEFAULT_SEED)
+        );
+        assert_ne!(generate(day, 10, DEFAULT_SEED), generate(day, 10, 7));
+        assert!(generate(Day::new(25, false), 10, DEFAULT_SEED).is_none());
+        assert!(generate(Day::in_year(2015, 5, false), 10, DEFAULT_SEED).is_none());
+    }
+
+    #[test]
+    fn small() {
+        let lines = generate_small(Day::new(5, true), DEFAULT_SEED).unwrap();
+        let largest = lines
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .filter_map(|num| num.parse::<i64>().ok())
+            .max();
+        assert!(largest < Some(100));
+        assert_eq!(
+            10,
+            generate_small(Day::new(7, true), DEFAULT_SEED)
+                .unwrap()
+                .len()
+        );
+        assert!(generate_small(Day::new(25, false), DEFAULT_SEED).is_none());
+    }
+
+    #[test_case(1 ; "when one line")]
+    #[test_case(50 ; "when several lines")]
+    fn solvable(size: usize) {
+        solutions().into_iter().for_each(|solution| {
+            (0..5).for_each(|seed| {
+                let lines = generate(solution.day, size, seed)
+                    .unwrap_or_else(|| panic!("no generator for {}", solution.day));
+                let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+                if let Err(err) = (solution.run)(&input) {
+                    panic!("{} failed on seed {}: {}", solution.day, seed, err);
+                }
+            })
+        });
+    }
+}
@@ -0,0 +1,91 @@
+// Conventions for where puzzle inputs live on disk
+use crate::{Day, DEFAULT_YEAR};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+// Example inputs are committed alongside the crate
+pub const EXAMPLE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+// Real inputs are personal, so they are looked up relative to the working directory
+pub const DEFAULT_INPUT_DIR: &str = "bench_inputs";
+
+// Each year has its own directory, <dir>/YYYY. The default year's inputs may
+// also sit directly in dir, as they did before there were other years
+pub fn year_dir(dir: impl AsRef<Path>, year: u16) -> PathBuf {
+    let dir = dir.as_ref();
+    let year_dir = dir.join(year.to_string());
+    if year == DEFAULT_YEAR && !year_dir.is_dir() {
+        dir.to_path_buf()
+    } else {
+        year_dir
+    }
+}
+
+// inputs/YYYY/dayN_ex.dat, or dayNp2_ex.dat for part two when that file exists.
+// make_tests! doesn't look on disk, it needs INPUT_OVERRIDES or EXAMPLE names instead
+pub fn example_path(dir: impl AsRef<Path>, day: Day) -> PathBuf {
+    let dir = year_dir(dir, day.year);
+    if day.part_two {
+        let path = dir.join(format!("day{}p2_ex.dat", day.number));
+        if path.exists() {
+            return path;
+        }
+    }
+
+    dir.join(format!("day{}_ex.dat", day.number))
+}
+
+// <dir>/YYYY/dayN.dat, shared by both parts
+pub fn input_path(dir: impl AsRef<Path>, day: Day) -> PathBuf {
+    year_dir(dir, day.year).join(format!("day{}.dat", day.number))
+}
+
+pub fn read_lines(path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(path)?).lines().collect()
+}
+
+pub fn read_stdin() -> io::Result<Vec<String>> {
+    io::stdin().lines().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{example_path, input_path, year_dir, EXAMPLE_DIR};
+    use crate::Day;
+    use std::path::PathBuf;
+    use test_case::test_case;
+
+    #[test_case(Day::new(5, false) => "day5_ex.dat")]
+    #[test_case(Day::new(5, true) => "day5_ex.dat" ; "when no part two example")]
+    #[test_case(Day::new(1, true) => "day1p2_ex.dat" ; "when part two example")]
+    fn example(day: Day) -> String {
+        let path = example_path(EXAMPLE_DIR, day);
+        assert!(path.exists());
+        path.file_name().unwrap().to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn input() {
+        assert_eq!(
+            PathBuf::from("dir/day5.dat"),
+            input_path("dir", Day::new(5, true))
+        );
+        assert_eq!(
+            PathBuf::from("dir/2015/day5.dat"),
+            input_path("dir", Day::in_year(2015, 5, true))
+        );
+    }
+
+    #[test]
+    fn year() {
+        assert_eq!(
+            PathBuf::from(EXAMPLE_DIR).join("2023"),
+            year_dir(EXAMPLE_DIR, 2023)
+        );
+        assert_eq!(PathBuf::from("dir"), year_dir("dir", 2023));
+        assert_eq!(PathBuf::from("dir/2024"), year_dir("dir", 2024));
+    }
+}
@@ -1,12 +1,14 @@
+use crate::AocError;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::char,
-    combinator::peek,
-    error::{Error, ErrorKind},
+    combinator::{all_consuming, peek},
+    error::{ContextError, ErrorKind, ParseError},
     sequence::terminated,
-    Err as nErr, IResult,
+    Err as nErr,
 };
+use rayon::prelude::*;
 
 pub mod day1;
 pub mod day1p2;
@@ -15,8 +17,58 @@ pub mod day3;
 pub mod day4;
 pub mod day5;
 pub mod day6;
+pub mod day7;
 
-pub fn parse_spelled_digit(input: &str) -> IResult<&str, i64> {
+// nom error for the day parsers. Keeps where parsing stopped, the char expected there
+// if there was one, and the context() names it was inside
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Error<'a> {
+    pub input: &'a str,
+    pub kind: ErrorKind,
+    pub expected: Option<char>,
+    // Innermost first, nom adds them as the error is returned
+    pub contexts: Vec<&'static str>,
+}
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Error<'a>>;
+
+impl<'a> Error<'a> {
+    pub fn new(input: &'a str, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            expected: None,
+            contexts: vec![],
+        }
+    }
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, kind)
+    }
+
+    // The innermost error points at the token that didn't match, keep it
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, expected: char) -> Self {
+        Self {
+            expected: Some(expected),
+            ..Self::new(input, ErrorKind::Char)
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(_: &'a str, context: &'static str, mut other: Self) -> Self {
+        other.contexts.push(context);
+        other
+    }
+}
+
+pub fn parse_spelled_digit(input: &str) -> IResult<'_, i64> {
     let (rest, num) = alt((
         terminated(tag("on"), peek(char('e'))),
         terminated(tag("tw"), peek(char('o'))),
@@ -45,69 +97,102 @@ pub fn parse_spelled_digit(input: &str) -> IResult<&str, i64> {
     Ok((rest, num))
 }
 
-pub fn parse_literal_digit(input: &str) -> IResult<&str, i64> {
-    if input.is_empty() {
-        return Err(nom::Err::Error(Error::new(input, ErrorKind::Digit)));
-    }
-
-    let zero = 48;
-    let nine = 57;
+// Numeric value of an ASCII digit byte. Every byte of a multi-byte UTF-8
+// character is >= 0x80, so scanning bytes never mistakes one for a digit
+pub fn ascii_digit(byte: u8) -> Option<i64> {
+    byte.is_ascii_digit().then(|| (byte - b'0') as i64)
+}
 
-    let digit: u8 = unsafe {
-        input
-            .chars()
-            .next()
-            .unwrap_unchecked()
-            .try_into()
-            .unwrap_unchecked()
-    };
+// Split input after its leading run of ASCII digits
+pub fn scan_digits(input: &str) -> (&str, &str) {
+    let len = input.bytes().take_while(u8::is_ascii_digit).count();
+    // The split point follows an ASCII byte, so it is always a char boundary
+    input.split_at(len)
+}
 
-    if digit < zero || digit > nine {
-        return Err(nom::Err::Error(Error::new(input, ErrorKind::Digit)));
+pub fn parse_literal_digit(input: &str) -> IResult<'_, i64> {
+    match input.bytes().next().and_then(ascii_digit) {
+        Some(digit) => Ok((&input[1..], digit)),
+        None => Err(nErr::Error(Error::new(input, ErrorKind::Digit))),
     }
-    let rest = unsafe { input.get_unchecked(1..) };
-    Ok((rest, (digit - zero) as i64))
 }
 
-pub fn parse_int(input: &str) -> IResult<&str, i64> {
-    if input.is_empty() {
-        return Err(nom::Err::Error(Error::new(input, ErrorKind::Digit)));
+pub fn parse_int(input: &str) -> IResult<'_, i64> {
+    let (digits, rest) = scan_digits(input);
+    if digits.is_empty() {
+        return Err(nErr::Error(Error::new(input, ErrorKind::Digit)));
     }
 
-    let zero = 48;
-    let nine = 57;
+    digits
+        .bytes()
+        .try_fold(0i64, |accum, digit| {
+            accum.checked_mul(10)?.checked_add((digit - b'0') as i64)
+        })
+        .map(|num| (rest, num))
+        .ok_or_else(|| nErr::Error(Error::new(input, ErrorKind::TooLarge)))
+}
 
-    let mut chars = input.chars();
-    let first: u8 = unsafe {
-        chars
-            .next()
-            .unwrap_unchecked()
-            .try_into()
-            .unwrap_unchecked()
-    };
-    if first < zero || first > nine {
-        return Err(nom::Err::Error(Error::new(input, ErrorKind::Digit)));
-    }
+pub fn parse_digit(input: &str) -> IResult<'_, i64> {
+    alt((parse_spelled_digit, parse_literal_digit))(input)
+}
 
-    let mut taken = 1;
-    let mut accum = (first - zero) as i64;
+// Split the input on blank lines, with the index of each section's first line
+pub fn sections<'a>(input: &'a [&'a str]) -> Vec<(usize, &'a [&'a str])> {
+    let mut sections = vec![];
+    let mut start = 0;
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim().is_empty())
+        .map(|(idx, _)| idx)
+        .chain([input.len()])
+        .for_each(|end| {
+            if start < end {
+                sections.push((start, &input[start..end]));
+            }
+            start = end + 1;
+        });
+    sections
+}
 
-    for digit in chars {
-        let digit_ascii: u8 = unsafe { digit.try_into().unwrap_unchecked() };
-        if digit_ascii < zero || digit_ascii > nine {
-            break;
-        }
-        taken += 1;
-        accum *= 10;
-        accum += (digit_ascii - zero) as i64;
-    }
+// parse_line on all of line, anything it leaves over is an error
+pub fn whole_line<'a, T>(
+    line: &'a str,
+    parse_line: impl Fn(&'a str) -> IResult<'a, T>,
+) -> Result<T, nErr<Error<'a>>> {
+    all_consuming(parse_line)(line).map(|(_, value)| value)
+}
 
-    let rest = unsafe { input.get_unchecked(taken..) };
-    Ok((rest, accum))
+// Every line parsed in order, or an error for the first line parse_line can't parse all of
+pub fn parse_lines<'a, T: Send>(
+    input: &[&'a str],
+    context: &'static str,
+    parse_line: impl Fn(&'a str) -> IResult<'a, T> + Sync,
+) -> Result<Vec<T>, AocError> {
+    let lines: Vec<_> = input
+        .par_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            whole_line(line, &parse_line).map_err(|err| AocError::parse(idx, line, context, err))
+        })
+        .collect();
+    lines.into_iter().collect()
 }
 
-pub fn parse_digit(input: &str) -> IResult<&str, i64> {
-    alt((parse_spelled_digit, parse_literal_digit))(input)
+// The first line parse_line fails on or doesn't parse all of, for days that skip those lines
+pub fn check_lines<'a, T>(
+    input: &[&'a str],
+    context: &'static str,
+    parse_line: impl Fn(&'a str) -> IResult<'a, T> + Sync,
+) -> Result<(), AocError> {
+    input
+        .par_iter()
+        .enumerate()
+        .find_map_first(|(idx, line)| {
+            let err = whole_line(line, &parse_line).err()?;
+            Some(AocError::parse(idx, line, context, err))
+        })
+        .map_or(Ok(()), Err)
 }
 
 #[cfg(test)]
@@ -132,6 +217,8 @@ mod test {
     #[test_case("" ; "when empty")]
     #[test_case("one" ; "when written in english")]
     #[test_case("!@#$@#" ; "when symbols")]
+    #[test_case("٣" ; "when non ascii digit")]
+    #[test_case("é" ; "when non ascii")]
     #[should_panic]
     fn parse_literal_digit_panics(input: &str) {
         super::parse_literal_digit(input).unwrap();
@@ -148,6 +235,8 @@ mod test {
     #[test_case("9", 9 => "")]
     #[test_case("9a", 9 => "a")]
     #[test_case("134", 134 => "")]
+    #[test_case("12é", 12 => "é")]
+    #[test_case("9223372036854775807", i64::MAX => "")]
     fn parse_int(input: &str, output: i64) -> &str {
         let (rest, num) = super::parse_int(input).unwrap();
         assert_eq!(output, num);
@@ -157,6 +246,9 @@ mod test {
     #[test_case("" ; "when empty")]
     #[test_case("one" ; "when written in english")]
     #[test_case("!@#$@#" ; "when symbols")]
+    #[test_case("٣" ; "when non ascii digit")]
+    #[test_case("é1" ; "when non ascii prefix")]
+    #[test_case("99999999999999999999" ; "when too large")]
     #[should_panic]
     fn parse_int_panics(input: &str) {
         super::parse_int(input).unwrap();
@@ -217,4 +309,88 @@ mod test {
     fn parse_digit_panics(input: &str) {
         super::parse_digit(input).unwrap();
     }
+
+    #[test_case(&[] => Vec::<(usize, Vec<&str>)>::new() ; "when empty")]
+    #[test_case(&["a", "b"] => vec![(0, vec!["a", "b"])] ; "when one section")]
+    #[test_case(&["a", "", "b", "c"] => vec![(0, vec!["a"]), (2, vec!["b", "c"])] ; "when two sections")]
+    #[test_case(&["", "a", " ", "", "b", ""] => vec![(1, vec!["a"]), (4, vec!["b"])] ; "when extra blank lines")]
+    fn sections<'a>(input: &'a [&'a str]) -> Vec<(usize, Vec<&'a str>)> {
+        super::sections(input)
+            .into_iter()
+            .map(|(offset, section)| (offset, section.to_vec()))
+            .collect()
+    }
+
+    #[test_case(&["1", "22", "333"] => Ok(vec![1, 22, 333]) ; "when all parse")]
+    #[test_case(&[] => Ok(vec![]) ; "when empty")]
+    #[test_case(&["1", "x", "2 3"] => Err(String::from("line 2, column 1: could not parse ints, expected a number")) ; "when first is bad")]
+    #[test_case(&["1", "2 3", "x"] => Err(String::from("line 2, column 2: could not parse ints, expected the end of the line")) ; "when first is partial")]
+    fn parse_lines(input: &[&str]) -> Result<Vec<i64>, String> {
+        super::parse_lines(input, "ints", super::parse_int).map_err(|err| err.to_string())
+    }
+
+    // Every parser must reject or skip arbitrary Unicode without panicking,
+    // and a successful parse must only ever consume a prefix of its input
+    mod unicode {
+        use crate::parser::*;
+        use proptest::prelude::*;
+
+        // Arbitrary strings mixed with the ASCII the parsers look for
+        const MIXED: &str = "[0-9a-z٣é🎄 .:|#*,;-]{0,40}";
+
+        fn check<T>(input: &str, res: IResult<'_, T>) -> Result<(), TestCaseError> {
+            if let Ok((rest, _)) = res {
+                prop_assert!(input.ends_with(rest));
+            }
+            Ok(())
+        }
+
+        fn check_all(input: &str) -> Result<(), TestCaseError> {
+            check(input, parse_spelled_digit(input))?;
+            check(input, parse_literal_digit(input))?;
+            check(input, parse_int(input))?;
+            check(input, parse_digit(input))?;
+            check(input, day1::parse_line(input))?;
+            check(input, day1p2::parse_glob_then_digit(input))?;
+            check(input, day1p2::parse_line(input))?;
+            for (_, num) in [day1::parse_line(input), day1p2::parse_line(input)]
+                .into_iter()
+                .flatten()
+            {
+                prop_assert!((0..100).contains(&num));
+            }
+            check(input, day2::parse_line(input))?;
+            check(input, day3::parse_line(input))?;
+            check(input, day4::parse_line(input))?;
+            check(input, day7::parse_line(input))?;
+            let _ = day3::parse_input(&[input, input]);
+            let _ = day5::parse_input(&[input, "", input, input]);
+            let _ = day6::parse_input(&[input, input]);
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn arbitrary(input in any::<String>()) {
+                check_all(&input)?;
+            }
+
+            #[test]
+            fn mixed(input in MIXED) {
+                check_all(&input)?;
+            }
+
+            #[test]
+            fn parse_int_matches_std(input in MIXED) {
+                let digits: String = input.chars().take_while(char::is_ascii_digit).collect();
+                match parse_int(&input) {
+                    Ok((rest, num)) => {
+                        prop_assert_eq!(Ok(num), digits.parse::<i64>());
+                        prop_assert_eq!(&input[digits.len()..], rest);
+                    }
+                    Err(_) => prop_assert!(digits.parse::<i64>().is_err()),
+                }
+            }
+        }
+    }
 }
@@ -1,27 +1,36 @@
-pub fn parse_line(input: &str) -> i64 {
-    let zero = 48;
-    let nine = 57;
+use super::{ascii_digit, Error, IResult};
+use nom::{error::ErrorKind, Err as nErr};
 
-    let mut number = 0;
+// First and last digit of the line as a two digit number, the whole line is used
+pub fn parse_line(input: &str) -> IResult<'_, i64> {
+    let bytes = input.as_bytes();
+    let first = bytes.iter().copied().find_map(ascii_digit);
+    let last = bytes.iter().rev().copied().find_map(ascii_digit);
 
-    for ch in input.chars() {
-        let digit: u8 = unsafe { ch.try_into().unwrap_unchecked() };
-        if digit < zero || digit > nine {
-            continue;
-        }
-        number = (digit - zero) as i64;
-        break;
+    match (first, last) {
+        (Some(first), Some(last)) => Ok(("", first * 10 + last)),
+        _ => Err(nErr::Error(Error::new(input, ErrorKind::Digit))),
     }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
 
-    for ch in input.chars().rev() {
-        let digit: u8 = unsafe { ch.try_into().unwrap_unchecked() };
-        if digit < zero || digit > nine {
-            continue;
-        }
-        number *= 10;
-        number += (digit - zero) as i64;
-        break;
+    #[test_case("12" => 12)]
+    #[test_case("1abc2" => 12)]
+    #[test_case("treb7uchet" => 77)]
+    #[test_case("a1b2c3d4e5f" => 15)]
+    #[test_case("é1ü٣2ß" => 12 ; "when non ascii")]
+    fn parse_line(input: &str) -> i64 {
+        super::parse_line(input).unwrap().1
     }
 
-    number
+    #[test_case("" ; "when empty")]
+    #[test_case("abc" ; "when no digits")]
+    #[test_case("é٣ü" ; "when only non ascii")]
+    #[should_panic]
+    fn parse_line_panics(input: &str) {
+        super::parse_line(input).unwrap();
+    }
 }
@@ -1,20 +1,23 @@
-use super::parse_digit;
+use super::{parse_digit, Error, IResult};
 use nom::{
     bytes::complete::take,
+    error::ErrorKind,
     multi::{many1, many_till},
-    IResult,
+    Err as nErr,
 };
 
-pub fn parse_glob_then_digit(input: &str) -> IResult<&str, i64> {
+pub fn parse_glob_then_digit(input: &str) -> IResult<'_, i64> {
     let (rest, (_, digit)) = many_till(take(1usize), parse_digit)(input)?;
     Ok((rest, digit))
 }
 
-pub fn parse_line(input: &str) -> i64 {
-    let (_, digits) = unsafe { many1(parse_glob_then_digit)(input).unwrap_unchecked() };
-    let first_digit = unsafe { *digits.get_unchecked(0) };
-    let second_digit = unsafe { *digits.get_unchecked(digits.len() - 1) };
-    first_digit * 10 + second_digit
+// First and last digit of the line as a two digit number, the whole line is used
+pub fn parse_line(input: &str) -> IResult<'_, i64> {
+    match many1(parse_glob_then_digit)(input) {
+        Ok((_, digits)) => Ok(("", digits[0] * 10 + digits[digits.len() - 1])),
+        // Point at the start of the line, there was no digit anywhere in it
+        Err(_) => Err(nErr::Error(Error::new(input, ErrorKind::Digit))),
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +102,22 @@ mod test {
         (one, two)
     }
 
+    #[test_case("two1nine" => 29)]
+    #[test_case("zoneight234" => 14)]
+    #[test_case("7pqrstsixteen" => 76)]
+    #[test_case("éone٣üsix" => 16 ; "when non ascii")]
+    fn parse_line(input: &str) -> i64 {
+        super::parse_line(input).unwrap().1
+    }
+
+    #[test_case("" ; "when empty")]
+    #[test_case("abc" ; "when no digits")]
+    #[test_case("é٣üon" ; "when only non ascii")]
+    #[should_panic]
+    fn parse_line_panics(input: &str) {
+        super::parse_line(input).unwrap();
+    }
+
     #[test_case("" ; "when empty")]
     #[test_case("abc" ; "when letters")]
     #[should_panic]
@@ -1,21 +1,24 @@
-use super::parse_int;
+use super::{parse_int, IResult};
 use crate::types::{Game, GameSet};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{char, space0},
+    error::context,
     multi::many0,
     sequence::{preceded, terminated, tuple},
-    IResult,
 };
 
-pub fn parse_game_id(input: &str) -> IResult<&str, i64> {
-    terminated(preceded(tag("Game "), parse_int), tag(":"))(input)
+pub fn parse_game_id(input: &str) -> IResult<'_, i64> {
+    context(
+        "game id",
+        terminated(preceded(tag("Game "), parse_int), tag(":")),
+    )(input)
 }
 
-pub fn parse_game_cubes(input: &str) -> IResult<&str, GameSet> {
-    let (rest, num) = terminated(parse_int, char(' '))(input)?;
-    let (rest, color) = alt((tag("red"), tag("green"), tag("blue")))(rest)?;
+pub fn parse_game_cubes(input: &str) -> IResult<'_, GameSet> {
+    let (rest, num) = context("cube count", terminated(parse_int, char(' ')))(input)?;
+    let (rest, color) = context("color", alt((tag("red"), tag("green"), tag("blue"))))(rest)?;
 
     let mut set = GameSet::default();
     match color {
@@ -27,7 +30,7 @@ pub fn parse_game_cubes(input: &str) -> IResult<&str, GameSet> {
     Ok((rest, set))
 }
 
-pub fn parse_game_set(input: &str) -> IResult<&str, GameSet> {
+pub fn parse_game_set(input: &str) -> IResult<'_, GameSet> {
     let (rest, mut sets) = many0(tuple((space0, parse_game_cubes, tag(","))))(input)?;
     let (rest, (_, last)) = tuple((space0, parse_game_cubes))(rest)?;
     sets.push(("", last, ""));
@@ -44,14 +47,14 @@ pub fn parse_game_set(input: &str) -> IResult<&str, GameSet> {
     Ok((rest, set))
 }
 
-pub fn parse_game_rounds(input: &str) -> IResult<&str, Vec<GameSet>> {
+pub fn parse_game_rounds(input: &str) -> IResult<'_, Vec<GameSet>> {
     let (rest, mut vec) = many0(terminated(parse_game_set, tag(";")))(input)?;
     let (rest, last) = parse_game_set(rest)?;
     vec.push(last);
     Ok((rest, vec))
 }
 
-pub fn parse_line(input: &str) -> IResult<&str, Game> {
+pub fn parse_line(input: &str) -> IResult<'_, Game> {
     let (rest, (id, sets)) = tuple((parse_game_id, parse_game_rounds))(input)?;
     Ok((rest, Game { id, sets }))
 }
@@ -1,19 +1,22 @@
-use super::parse_int;
-use crate::types::{Schematic, Span};
+use super::{parse_int, Error, IResult};
+use crate::{
+    types::{Schematic, Span},
+    AocError,
+};
 use nom::{
     character::complete::{char, none_of},
-    error::{Error, ErrorKind},
+    error::ErrorKind,
     multi::many0,
-    Err as nErr, IResult,
+    Err as nErr,
 };
 use rayon::prelude::*;
 
-pub fn parse_periods(input: &str) -> IResult<&str, usize> {
+pub fn parse_periods(input: &str) -> IResult<'_, usize> {
     let (rest, periods) = many0(char('.'))(input)?;
     Ok((rest, periods.len()))
 }
 
-pub fn parse_number(input: &str) -> IResult<&str, (usize, i64)> {
+pub fn parse_number(input: &str) -> IResult<'_, (usize, i64)> {
     let (rest, number) = parse_int(input)?;
 
     if number == 0 {
@@ -23,11 +26,11 @@ pub fn parse_number(input: &str) -> IResult<&str, (usize, i64)> {
     Ok((rest, (number.ilog10() as usize + 1, number)))
 }
 
-pub fn parse_symbol(input: &str) -> IResult<&str, char> {
+pub fn parse_symbol(input: &str) -> IResult<'_, char> {
     none_of(".1234567890")(input)
 }
 
-pub fn parse_item(input: &str) -> IResult<&str, Schematic> {
+pub fn parse_item(input: &str) -> IResult<'_, Schematic> {
     let (rest, offset) = parse_periods(input)?;
 
     match parse_symbol(rest) {
@@ -38,7 +41,7 @@ pub fn parse_item(input: &str) -> IResult<&str, Schematic> {
     }
 }
 
-pub fn parse_line(input: &str) -> IResult<&str, Vec<Schematic>> {
+pub fn parse_line(input: &str) -> IResult<'_, Vec<Schematic>> {
     let (rest, mut items) = many0(parse_item)(input)?;
 
     // Adjust the span on each item to show proper place in line
@@ -57,23 +60,25 @@ pub fn parse_line(input: &str) -> IResult<&str, Vec<Schematic>> {
     Ok((rest, items))
 }
 
-pub fn parse_input(lines: &[&str]) -> IResult<(), ((usize, usize), Vec<Schematic>)> {
+pub fn parse_input(lines: &[&str]) -> Result<((usize, usize), Vec<Schematic>), AocError> {
     let height = lines.len();
     if height == 0 {
-        return Ok(((), ((0, 0), vec![])));
+        return Ok(((0, 0), vec![]));
     }
     let width = lines[0].chars().count();
 
     let lines: Vec<_> = lines
         .par_iter()
-        .map(|line| parse_line(line).map(|(_, vec)| vec))
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_line(line)
+                .map(|(_, vec)| vec)
+                .map_err(|err| AocError::parse(idx, line, "schematic", err))
+        })
         .collect();
 
     // Return error if any of the lines had an error
-    let mut lines: Vec<Vec<_>> = lines
-        .into_iter()
-        .try_collect()
-        .map_err(|err| err.map(|err| Error::new((), err.code)))?;
+    let mut lines: Vec<Vec<_>> = lines.into_iter().try_collect()?;
 
     // Adjust each span's line to be given the point
     lines.par_iter_mut().enumerate().for_each(|(y, line)| {
@@ -85,7 +90,7 @@ pub fn parse_input(lines: &[&str]) -> IResult<(), ((usize, usize), Vec<Schematic
         });
     });
 
-    Ok(((), ((width, height), lines.into_iter().flatten().collect())))
+    Ok(((width, height), lines.into_iter().flatten().collect()))
 }
 
 #[cfg(test)]
@@ -1,46 +1,100 @@
-use super::parse_int;
-use crate::types::Day5;
+use super::{parse_int, IResult};
+use crate::{
+    types::{Almanac, CategoryMap},
+    AocError,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, space1},
+    combinator::all_consuming,
+    error::context,
     multi::many1,
     sequence::{preceded, tuple},
-    IResult,
 };
 use std::ops::Range;
 
-fn parse_seeds(input: &str) -> IResult<&str, Vec<i64>> {
+fn parse_seeds(input: &str) -> IResult<'_, Vec<i64>> {
     let number_parser = preceded(space1, parse_int);
-    let (rest, vec) = preceded(tag("seeds:"), many1(number_parser))(input)?;
+    let (rest, vec) = context("seeds", preceded(tag("seeds:"), many1(number_parser)))(input)?;
     Ok((rest, vec))
 }
 
 // (out.0-to-out.1 map)
-fn parse_map_name(input: &str) -> IResult<&str, (String, String)> {
-    let (rest, (first, _, second, _, _)) =
-        tuple((alpha1, tag("-to-"), alpha1, space1, tag("map:")))(input)?;
+fn parse_map_name(input: &str) -> IResult<'_, (String, String)> {
+    let (rest, (first, _, second, _, _)) = context(
+        "map title",
+        tuple((alpha1, tag("-to-"), alpha1, space1, tag("map:"))),
+    )(input)?;
     Ok((rest, (first.to_owned(), second.to_owned())))
 }
 
 // Source range, destination range
-fn parse_range(input: &str) -> IResult<&str, (Range<i64>, i64)> {
+fn parse_range(input: &str) -> IResult<'_, (Range<i64>, i64)> {
     let parse_src = preceded(space1, parse_int);
     let parse_len = preceded(space1, parse_int);
-    let (rest, (dest_start, src_start, len)) = tuple((parse_int, parse_src, parse_len))(input)?;
+    let (rest, (dest_start, src_start, len)) =
+        context("range", tuple((parse_int, parse_src, parse_len)))(input)?;
     Ok((rest, (src_start..src_start + len, dest_start)))
 }
 
-pub fn parse_line(input: &str) -> IResult<&str, Day5> {
-    if let Ok((rest, vec)) = parse_seeds(input) {
-        return Ok((rest, Day5::Seeds(vec)));
-    }
+// A map's title line then its ranges, line numbers are relative to the title
+fn parse_map(input: &[&str]) -> Result<CategoryMap, AocError> {
+    let Some((title, ranges)) = input.split_first() else {
+        return Err(AocError::input("almanac", "a map is missing its title"));
+    };
+    let (_, (from, to)) = all_consuming(parse_map_name)(title)
+        .map_err(|err| AocError::parse(0, title, "almanac", err))?;
+    let ranges = ranges
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            all_consuming(parse_range)(line)
+                .map(|(_, range)| range)
+                .map_err(|err| AocError::parse(idx + 1, line, "almanac", err))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(CategoryMap { from, to, ranges })
+}
 
-    if let Ok((rest, titles)) = parse_map_name(input) {
-        return Ok((rest, Day5::MapTitle(titles.0, titles.1)));
+// The seeds on the first line, then blank line separated maps,
+// in any order but with only one map from each category
+pub fn parse_input(input: &[&str]) -> Result<Almanac, AocError> {
+    let mut sections = super::sections(input).into_iter();
+    let (offset, seeds) = sections
+        .next()
+        .ok_or_else(|| AocError::input("almanac", "the almanac is empty"))?;
+    let (line, rest) = seeds.split_first().expect("sections are never empty");
+    let (_, seeds) = all_consuming(parse_seeds)(line)
+        .map_err(|err| AocError::parse(offset, line, "almanac", err))?;
+    if let Some(line) = rest.first() {
+        return Err(AocError::input(
+            "almanac",
+            format!(
+                "line {}: expected a blank line after the seeds, not {:?}",
+                offset + 2,
+                line
+            ),
+        ));
     }
 
-    let (rest, ranges) = parse_range(input)?;
-    Ok((rest, Day5::Maps(ranges.0, ranges.1)))
+    let mut maps: Vec<CategoryMap> = vec![];
+    sections.try_for_each(|(offset, section)| {
+        let map = parse_map(section).map_err(|err| err.at_offset(offset))?;
+        if maps.iter().any(|other| other.from == map.from) {
+            return Err(AocError::input(
+                "almanac",
+                format!(
+                    "line {}: there is already a map from {}",
+                    offset + 1,
+                    map.from
+                ),
+            ));
+        }
+        maps.push(map);
+        Ok(())
+    })?;
+
+    Ok(Almanac { seeds, maps })
 }
 
 #[cfg(test)]
@@ -96,4 +150,25 @@ mod test {
     fn parse_range_panics(input: &str) {
         super::parse_range(input).unwrap();
     }
+
+    #[test_case(&["seeds: 1 2", "", "seed-to-soil map:", "50 98 2", "52 50 48"] => vec![2] ; "when one map")]
+    #[test_case(&["seeds: 1 2", "", "seed-to-soil map:", "", "soil-to-water map:", "1 2 3"] => vec![0, 1] ; "when empty map")]
+    #[test_case(&["", "seeds: 1 2", "", "", "a-to-b map:", "1 2 3", "", "b-to-c map:", "1 2 3"] => vec![1, 1] ; "when extra blank lines")]
+    #[test_case(&["seeds: 1 2"] => Vec::<usize>::new() ; "when no maps")]
+    fn parse_input(input: &[&str]) -> Vec<usize> {
+        let almanac = super::parse_input(input).unwrap();
+        assert_eq!(vec![1, 2], almanac.seeds);
+        almanac.maps.iter().map(|map| map.ranges.len()).collect()
+    }
+
+    #[test_case(&[] => "almanac: the almanac is empty" ; "when empty")]
+    #[test_case(&["seed-to-soil map:", "50 98 2"] => "line 1, column 1: could not parse almanac > seeds, expected a keyword" ; "when no seeds")]
+    #[test_case(&["seeds: 1 2", "seed-to-soil map:"] => r#"almanac: line 2: expected a blank line after the seeds, not "seed-to-soil map:""# ; "when no blank line")]
+    #[test_case(&["seeds: 1 2", "", "seed-to-soil map:", "50 98"] => "line 4, column 6: could not parse almanac > range, expected a space" ; "when short range")]
+    #[test_case(&["seeds: 1 2", "", "seed-to-soil map:", "50 98 2 1"] => "line 4, column 8: could not parse almanac, expected the end of the line" ; "when long range")]
+    #[test_case(&["seeds: 1 2", "", "50 98 2"] => "line 3, column 1: could not parse almanac > map title, expected a word" ; "when no title")]
+    #[test_case(&["seeds: 1 2", "", "a-to-b map:", "", "a-to-c map:"] => "almanac: line 5: there is already a map from a" ; "when mapped twice")]
+    fn parse_input_errors(input: &[&str]) -> String {
+        super::parse_input(input).unwrap_err().to_string()
+    }
 }
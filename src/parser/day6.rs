@@ -1,16 +1,32 @@
-use super::parse_int;
+use super::{parse_int, whole_line};
+use crate::AocError;
 use nom::{bytes::complete::tag, character::complete::space0, multi::many1, sequence::preceded};
 
-pub fn parse_line(input: &[&str]) -> (Vec<i64>, Vec<i64>) {
-    let (rest, _) = tag::<_, _, nom::error::Error<&str>>("Time:")(input[0]).unwrap();
-    let (_, times) =
-        many1::<_, _, nom::error::Error<&str>, _>(preceded(space0, parse_int))(rest).unwrap();
-
-    let (rest, _) = tag::<_, _, nom::error::Error<&str>>("Distance:")(input[1]).unwrap();
-    let (_, records) =
-        many1::<_, _, nom::error::Error<&str>, _>(preceded(space0, parse_int))(rest).unwrap();
+// Parse all of the idx'th line as <title> followed by a list of numbers
+fn parse_row(input: &[&str], idx: usize, title: &'static str) -> Result<Vec<i64>, AocError> {
+    let line = input
+        .get(idx)
+        .ok_or_else(|| AocError::input(title, "line is missing"))?;
+    whole_line(line, |line| {
+        preceded(tag(title), many1(preceded(space0, parse_int)))(line)
+    })
+    .map_err(|err| AocError::parse(idx, line, title, err))
+}
 
-    (times, records)
+pub fn parse_input(input: &[&str]) -> Result<(Vec<i64>, Vec<i64>), AocError> {
+    let times = parse_row(input, 0, "Time:")?;
+    let records = parse_row(input, 1, "Distance:")?;
+    if times.len() != records.len() {
+        return Err(AocError::input(
+            "races",
+            format!(
+                "{} times but {} distances, every race needs both",
+                times.len(),
+                records.len()
+            ),
+        ));
+    }
+    Ok((times, records))
 }
 
 #[cfg(test)]
@@ -18,7 +34,20 @@ mod test {
     use test_case::test_case;
 
     #[test_case(&["Time:      7  15   30", "Distance:  9  40  200"] => (vec![7, 15, 30], vec![9, 40, 200]))]
-    fn parse_line(input: &[&str]) -> (Vec<i64>, Vec<i64>) {
-        super::parse_line(input)
+    fn parse_input(input: &[&str]) -> (Vec<i64>, Vec<i64>) {
+        super::parse_input(input).unwrap()
+    }
+
+    #[test_case(&[] ; "when empty")]
+    #[test_case(&["Time:      7  15   30"] ; "when no distances")]
+    #[test_case(&["Distance:  9  40  200", "Time:      7  15   30"] ; "when swapped")]
+    #[test_case(&["Time:", "Distance:"] ; "when no numbers")]
+    #[test_case(&["Time:      7", "Distance:  9 x"] ; "when trailing junk")]
+    #[test_case(&["Time:      7  15 x", "Distance:  9  40"] ; "when trailing junk on times")]
+    #[test_case(&["Time:      7  15", "Distance:  9"] ; "when fewer distances")]
+    #[test_case(&["Time:      7", "Distance:  9  40"] ; "when fewer times")]
+    #[should_panic]
+    fn parse_input_panics(input: &[&str]) {
+        super::parse_input(input).unwrap();
     }
 }
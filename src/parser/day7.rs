@@ -0,0 +1,110 @@
+use super::{parse_int, IResult};
+use crate::types::Hand;
+use nom::{
+    character::complete::{one_of, space1},
+    error::context,
+    multi::count,
+    sequence::separated_pair,
+};
+
+pub fn parse_card(input: &str) -> IResult<'_, u8> {
+    let (rest, card) = one_of("23456789TJQKA")(input)?;
+    let value = match card {
+        'T' => 10,
+        'J' => Hand::JACK,
+        'Q' => 12,
+        'K' => 13,
+        'A' => 14,
+        digit => digit as u8 - b'0',
+    };
+    Ok((rest, value))
+}
+
+pub fn parse_hand(input: &str) -> IResult<'_, Hand> {
+    let (rest, cards) = context("hand", count(parse_card, 5))(input)?;
+    let cards = [cards[0], cards[1], cards[2], cards[3], cards[4]];
+    Ok((rest, Hand::new(cards)))
+}
+
+pub fn parse_line(input: &str) -> IResult<'_, (Hand, i64)> {
+    separated_pair(parse_hand, space1, context("bid", parse_int))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{Hand, HandKind};
+    use test_case::test_case;
+
+    #[test_case("2" => 2)]
+    #[test_case("9" => 9)]
+    #[test_case("T" => 10)]
+    #[test_case("J" => 11)]
+    #[test_case("Q" => 12)]
+    #[test_case("K" => 13)]
+    #[test_case("A" => 14)]
+    fn parse_card(input: &str) -> u8 {
+        super::parse_card(input).unwrap().1
+    }
+
+    #[test_case("" ; "when empty")]
+    #[test_case("1" ; "when one")]
+    #[test_case("a" ; "when lowercase")]
+    #[should_panic]
+    fn parse_card_panics(input: &str) {
+        super::parse_card(input).unwrap();
+    }
+
+    #[test_case("AAAAA" => HandKind::FiveOfAKind)]
+    #[test_case("AA8AA" => HandKind::FourOfAKind)]
+    #[test_case("23332" => HandKind::FullHouse)]
+    #[test_case("TTT98" => HandKind::ThreeOfAKind)]
+    #[test_case("23432" => HandKind::TwoPair)]
+    #[test_case("A23A4" => HandKind::OnePair)]
+    #[test_case("23456" => HandKind::HighCard)]
+    fn parse_hand(input: &str) -> HandKind {
+        super::parse_hand(input).unwrap().1.kind
+    }
+
+    #[test_case("" ; "when empty")]
+    #[test_case("AAAA" ; "when too short")]
+    #[test_case("AA1AA" ; "when invalid card")]
+    #[should_panic]
+    fn parse_hand_panics(input: &str) {
+        super::parse_hand(input).unwrap();
+    }
+
+    #[test_case("32T3K" => HandKind::OnePair)]
+    #[test_case("T55J5" => HandKind::FourOfAKind)]
+    #[test_case("KTJJT" => HandKind::FourOfAKind)]
+    #[test_case("QQQJA" => HandKind::FourOfAKind)]
+    #[test_case("JJJJJ" => HandKind::FiveOfAKind)]
+    #[test_case("2345J" => HandKind::OnePair)]
+    #[test_case("2245J" => HandKind::ThreeOfAKind)]
+    #[test_case("2244J" => HandKind::FullHouse)]
+    fn parse_hand_with_jokers(input: &str) -> HandKind {
+        super::parse_hand(input).unwrap().1.with_jokers().kind
+    }
+
+    #[test_case("33332", "2AAAA" ; "when same kind")]
+    #[test_case("77888", "77788" ; "when same kind later card")]
+    #[test_case("AAAAK", "22333" ; "when different kind")]
+    fn hand_ordering(stronger: &str, weaker: &str) {
+        let stronger: Hand = super::parse_hand(stronger).unwrap().1;
+        let weaker: Hand = super::parse_hand(weaker).unwrap().1;
+        assert!(stronger > weaker);
+    }
+
+    #[test_case("32T3K 765" => 765)]
+    #[test_case("QQQJA 483" => 483)]
+    fn parse_line(input: &str) -> i64 {
+        super::parse_line(input).unwrap().1 .1
+    }
+
+    #[test_case("" ; "when empty")]
+    #[test_case("32T3K" ; "when no bid")]
+    #[test_case("32T3K765" ; "when no space")]
+    #[should_panic]
+    fn parse_line_panics(input: &str) {
+        super::parse_line(input).unwrap();
+    }
+}
@@ -0,0 +1,360 @@
+// Piecewise offset maps over i64, for day 5 style range transforms
+use std::{fmt, ops::Range};
+
+// Numbers in each piece's range move by its offset, everything else maps to itself
+// Pieces are sorted, disjoint and non-empty
+#[derive(Default, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RangeMap {
+    pieces: Vec<(Range<i64>, i64)>,
+}
+
+// Sort intervals and merge any that overlap or touch, dropping empty ones
+pub fn normalize(intervals: impl IntoIterator<Item = Range<i64>>) -> Vec<Range<i64>> {
+    let mut intervals: Vec<_> = intervals
+        .into_iter()
+        .filter(|interval| !interval.is_empty())
+        .collect();
+    intervals.sort_unstable_by_key(|interval| interval.start);
+
+    let mut merged: Vec<Range<i64>> = vec![];
+    intervals
+        .into_iter()
+        .for_each(|interval| match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        });
+    merged
+}
+
+impl RangeMap {
+    // The map that leaves every number where it is
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    // From (source, destination start) lines as the almanac lists them,
+    // where lines overlap the first one listed wins
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a (Range<i64>, i64)>) -> Self {
+        let mut map = Self::identity();
+        lines.into_iter().for_each(|(src, dest)| {
+            let offset = dest - src.start;
+            let gaps = map
+                .segments(src.clone())
+                .into_iter()
+                .filter_map(|(range, offset)| offset.is_none().then_some(range));
+            map.pieces
+                .extend(gaps.map(|range| (range, offset)).collect::<Vec<_>>());
+            map.pieces.sort_unstable_by_key(|(range, _)| range.start);
+        });
+        map.normalize()
+    }
+
+    pub fn pieces(&self) -> &[(Range<i64>, i64)] {
+        &self.pieces
+    }
+
+    pub fn get(&self, num: i64) -> i64 {
+        let idx = self.pieces.partition_point(|(range, _)| range.end <= num);
+        match self.pieces.get(idx) {
+            Some((range, offset)) if range.contains(&num) => num + offset,
+            _ => num,
+        }
+    }
+
+    // range cut up by the pieces it crosses, with their offsets, or None between pieces
+    fn segments(&self, range: Range<i64>) -> Vec<(Range<i64>, Option<i64>)> {
+        let mut segments = vec![];
+        if range.is_empty() {
+            return segments;
+        }
+        let mut start = range.start;
+        let first = self.pieces.partition_point(|(piece, _)| piece.end <= start);
+        self.pieces[first..]
+            .iter()
+            .take_while(|(piece, _)| piece.start < range.end)
+            .for_each(|(piece, offset)| {
+                if start < piece.start {
+                    segments.push((start..piece.start, None));
+                }
+                let end = piece.end.min(range.end);
+                segments.push((start.max(piece.start)..end, Some(*offset)));
+                start = end;
+            });
+        if start < range.end {
+            segments.push((start..range.end, None));
+        }
+        segments
+    }
+
+    // Where a set of intervals ends up, as sorted disjoint intervals
+    pub fn apply(&self, intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        normalize(intervals.iter().flat_map(|interval| {
+            self.segments(interval.clone())
+                .into_iter()
+                .map(|(range, offset)| {
+                    let offset = offset.unwrap_or(0);
+                    range.start + offset..range.end + offset
+                })
+        }))
+    }
+
+    // Every number that ends up in the intervals, as sorted disjoint intervals
+    pub fn preimage(&self, intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        normalize(intervals.iter().flat_map(|interval| {
+            // Numbers between pieces stay put
+            let unmoved = self
+                .segments(interval.clone())
+                .into_iter()
+                .filter_map(|(range, offset)| offset.is_none().then_some(range));
+            let moved = self.pieces.iter().filter_map(|(range, offset)| {
+                let start = (range.start + offset).max(interval.start);
+                let end = (range.end + offset).min(interval.end);
+                (start < end).then(|| start - offset..end - offset)
+            });
+            unmoved.chain(moved).collect::<Vec<_>>()
+        }))
+    }
+
+    // The map that does self, then next
+    pub fn compose(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+        // Numbers self leaves alone only move as next moves them
+        let mut start = i64::MIN;
+        self.pieces
+            .iter()
+            .map(|(range, offset)| (range.clone(), *offset))
+            .chain([(i64::MAX..i64::MAX, 0)])
+            .for_each(|(range, offset)| {
+                next.segments(start..range.start)
+                    .into_iter()
+                    .filter_map(|(gap, next_offset)| Some((gap, next_offset?)))
+                    .for_each(|piece| pieces.push(piece));
+
+                let moved = range.start + offset..range.end + offset;
+                next.segments(moved)
+                    .into_iter()
+                    .for_each(|(image, next_offset)| {
+                        let domain = image.start - offset..image.end - offset;
+                        pieces.push((domain, offset + next_offset.unwrap_or(0)));
+                    });
+                start = range.end;
+            });
+
+        Self { pieces }.normalize()
+    }
+
+    // The map undoing self, if no two numbers end up in the same place
+    pub fn invert(&self) -> Option<Self> {
+        let images: Vec<_> = self
+            .pieces
+            .iter()
+            .map(|(range, offset)| range.start + offset..range.end + offset)
+            .collect();
+        let domains = normalize(self.pieces.iter().map(|(range, _)| range.clone()));
+
+        // The pieces must land on each other without overlapping, numbers
+        // between pieces stay put so nothing else may land there
+        let len = |intervals: &[Range<i64>]| -> i64 {
+            intervals
+                .iter()
+                .map(|interval| interval.end - interval.start)
+                .sum()
+        };
+        if normalize(images.clone()) != domains || len(&images) != len(&domains) {
+            return None;
+        }
+
+        let pieces = images
+            .into_iter()
+            .zip(&self.pieces)
+            .map(|(image, (_, offset))| (image, -offset))
+            .collect();
+        Some(Self { pieces }.normalize())
+    }
+
+    // Sort pieces, drop the ones that don't move anything
+    // and merge neighbours that move by the same amount
+    pub fn normalize(mut self) -> Self {
+        self.pieces
+            .retain(|(range, offset)| !range.is_empty() && *offset != 0);
+        self.pieces.sort_unstable_by_key(|(range, _)| range.start);
+
+        let mut pieces: Vec<(Range<i64>, i64)> = vec![];
+        self.pieces
+            .into_iter()
+            .for_each(|(range, offset)| match pieces.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end
+                }
+                _ => pieces.push((range, offset)),
+            });
+        Self { pieces }
+    }
+}
+
+// One piece per line, e.g. 98..100 -> 50..52
+impl fmt::Display for RangeMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pieces.iter().try_for_each(|(range, offset)| {
+            writeln!(
+                f,
+                "{:?} -> {:?}",
+                range,
+                range.start + offset..range.end + offset
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RangeMap;
+    use std::ops::Range;
+    use test_case::test_case;
+
+    // The example's seed-to-soil and soil-to-fertilizer maps
+    fn seed_to_soil() -> RangeMap {
+        RangeMap::from_lines(&[(98..100, 50), (50..98, 52)])
+    }
+
+    fn soil_to_fertilizer() -> RangeMap {
+        RangeMap::from_lines(&[(15..52, 0), (52..54, 37), (0..15, 39)])
+    }
+
+    #[test_case(&[] => Vec::<Range<i64>>::new() ; "when empty")]
+    #[test_case(&[5..7, 1..3, 2..4, 4..5, 9..9] => vec![1..7] ; "when overlapping")]
+    #[test_case(&[1..2, 3..4] => vec![1..2, 3..4] ; "when apart")]
+    fn normalize(intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        super::normalize(intervals.iter().cloned())
+    }
+
+    #[test_case(&[(98..100, 50), (50..98, 52)] => vec![(50..98, 2), (98..100, -48)] ; "when sorted")]
+    #[test_case(&[(0..10, 100), (5..15, 200)] => vec![(0..10, 100), (10..15, 195)] ; "when overlapping")]
+    #[test_case(&[(0..5, 0), (0..10, 10)] => vec![(5..10, 10)] ; "when earlier line is identity")]
+    #[test_case(&[(0..5, 10), (5..10, 15)] => vec![(0..10, 10)] ; "when merged")]
+    fn from_lines(lines: &[(Range<i64>, i64)]) -> Vec<(Range<i64>, i64)> {
+        RangeMap::from_lines(lines).pieces().to_vec()
+    }
+
+    #[test_case(79 => 81)]
+    #[test_case(14 => 14)]
+    #[test_case(98 => 50)]
+    #[test_case(99 => 51)]
+    #[test_case(100 => 100)]
+    fn get(num: i64) -> i64 {
+        seed_to_soil().get(num)
+    }
+
+    #[test_case(&[79..93, 55..68] => vec![57..70, 81..95] ; "when inside")]
+    #[test_case(&[90..110] => vec![50..52, 92..110] ; "when crossing")]
+    #[test_case(&[0..10] => vec![0..10] ; "when outside")]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn apply(intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        seed_to_soil().apply(intervals)
+    }
+
+    #[test_case(&[81..82] => vec![79..80] ; "when moved")]
+    #[test_case(&[48..52] => vec![48..50, 98..100] ; "when moved and unmoved")]
+    #[test_case(&[98..100] => vec![96..98] ; "when moved away")]
+    #[test_case(&[0..10, 100..101] => vec![0..10, 100..101] ; "when outside")]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn preimage(intervals: &[Range<i64>]) -> Vec<Range<i64>> {
+        seed_to_soil().preimage(intervals)
+    }
+
+    #[test_case(79 => 81)]
+    #[test_case(14 => 53)]
+    #[test_case(55 => 57)]
+    #[test_case(13 => 52)]
+    #[test_case(98 => 35)]
+    fn compose(seed: i64) -> i64 {
+        seed_to_soil().compose(&soil_to_fertilizer()).get(seed)
+    }
+
+    #[test]
+    fn invert() {
+        let map = seed_to_soil();
+        let inverse = map.invert().unwrap();
+        (40..110).for_each(|num| assert_eq!(num, inverse.get(map.get(num))));
+        assert_eq!(RangeMap::identity(), map.compose(&inverse));
+    }
+
+    #[test_case(&[(0..5, 10)] ; "when landing between pieces")]
+    #[test_case(&[(0..5, 10), (10..15, 12)] ; "when landing on each other")]
+    fn invert_none(lines: &[(Range<i64>, i64)]) {
+        assert_eq!(None, RangeMap::from_lines(lines).invert());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "50..98 -> 52..100\n98..100 -> 50..52\n",
+            seed_to_soil().to_string()
+        );
+    }
+
+    mod props {
+        use crate::range_map::RangeMap;
+        use proptest::prelude::*;
+        use std::{ops::Range, slice};
+
+        fn lines() -> impl Strategy<Value = Vec<(Range<i64>, i64)>> {
+            prop::collection::vec((0..60i64, 0..20i64, 0..60i64), 0..6).prop_map(|lines| {
+                lines
+                    .into_iter()
+                    .map(|(start, len, dest)| (start..start + len, dest))
+                    .collect()
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn get_matches_first_line(lines in lines(), num in -10..100i64) {
+                let expected = lines
+                    .iter()
+                    .find(|(src, _)| src.contains(&num))
+                    .map_or(num, |(src, dest)| num - src.start + dest);
+                prop_assert_eq!(expected, RangeMap::from_lines(&lines).get(num));
+            }
+
+            #[test]
+            fn apply_matches_get(lines in lines(), start in -10..100i64, len in 0..30i64) {
+                let map = RangeMap::from_lines(&lines);
+                let mut expected: Vec<_> = (start..start + len).map(|num| map.get(num)).collect();
+                expected.sort_unstable();
+                expected.dedup();
+                let applied: Vec<_> = map.apply(slice::from_ref(&(start..start + len))).into_iter().flatten().collect();
+                prop_assert_eq!(expected, applied);
+            }
+
+            #[test]
+            fn compose_matches_get(first in lines(), second in lines(), num in -10..150i64) {
+                let (first, second) = (RangeMap::from_lines(&first), RangeMap::from_lines(&second));
+                prop_assert_eq!(second.get(first.get(num)), first.compose(&second).get(num));
+            }
+
+            #[test]
+            fn preimage_matches_get(lines in lines(), start in -10..100i64, len in 0..30i64) {
+                let map = RangeMap::from_lines(&lines);
+                let target = start..start + len;
+                let expected: Vec<_> = (-40..200).filter(|num| target.contains(&map.get(*num))).collect();
+                let preimage: Vec<_> = map
+                    .preimage(slice::from_ref(&target))
+                    .into_iter()
+                    .flatten()
+                    .filter(|num| (-40..200).contains(num))
+                    .collect();
+                prop_assert_eq!(expected, preimage);
+            }
+
+            #[test]
+            fn invert_undoes(lines in lines(), num in -10..150i64) {
+                let map = RangeMap::from_lines(&lines);
+                if let Some(inverse) = map.invert() {
+                    prop_assert_eq!(num, inverse.get(map.get(num)));
+                    prop_assert_eq!(num, map.get(inverse.get(num)));
+                }
+            }
+        }
+    }
+}
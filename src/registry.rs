@@ -0,0 +1,91 @@
+// Registry of every solution, filled in by make_func! and aoc_day!
+use crate::{Answer, AocError, Day};
+use linkme::distributed_slice;
+
+// A day's parsed input, solving it runs the rest of the solution
+pub struct Parsed<'a>(Box<dyn FnOnce() -> Result<Answer, AocError> + Send + 'a>);
+
+impl<'a> Parsed<'a> {
+    pub fn new(solve: impl FnOnce() -> Result<Answer, AocError> + Send + 'a) -> Self {
+        Self(Box::new(solve))
+    }
+
+    pub fn solve(self) -> Result<Answer, AocError> {
+        (self.0)()
+    }
+}
+
+// A day's check_dayN function
+pub type CheckLines = fn(&[&str]) -> Result<(), AocError>;
+
+// A day's EXPLAIN function, describing how it came to its answer
+pub type Explain = fn(&[&str]) -> Result<String, AocError>;
+
+pub struct Solution {
+    pub day: Day,
+    // The day's try_dayN function
+    pub run: fn(&[&str]) -> Result<Answer, AocError>,
+    // The day's parse_dayN function, run then solve is the same as run
+    pub parse: for<'a> fn(&'a [&'a str]) -> Result<Parsed<'a>, AocError>,
+    // The day's check_dayN function if it skips lines it can't parse,
+    // errors on the first such line for strict runs
+    pub check_lines: Option<CheckLines>,
+    // Set with EXPLAIN, for aoc --explain
+    pub explain: Option<Explain>,
+    // Examples declared with the day, each read from inputs/YYYY/<name>_ex.dat
+    pub examples: &'static [&'static str],
+}
+
+#[distributed_slice]
+pub static SOLUTIONS: [Solution];
+
+// Every registered solution, ordered by day
+pub fn solutions() -> Vec<&'static Solution> {
+    let mut solutions: Vec<_> = SOLUTIONS.iter().collect();
+    solutions.sort_by_key(|solution| solution.day);
+    solutions
+}
+
+pub fn solution(day: Day) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{inputs, Day};
+
+    #[test]
+    fn unique() {
+        let solutions = super::solutions();
+        assert!(!solutions.is_empty());
+        assert!(solutions.windows(2).all(|pair| pair[0].day < pair[1].day));
+    }
+
+    #[test]
+    fn lookup() {
+        let day = Day::new(5, true);
+        assert_eq!(Some(day), super::solution(day).map(|solution| solution.day));
+        assert!(super::solution(Day::new(25, true)).is_none());
+    }
+
+    #[test]
+    fn phases() {
+        super::solutions().into_iter().for_each(|solution| {
+            let path = inputs::example_path(inputs::EXAMPLE_DIR, solution.day);
+            let lines = inputs::read_lines(path).unwrap();
+            let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+
+            if let Some(check) = solution.check_lines {
+                check(&input).unwrap_or_else(|err| panic!("{}: {}", solution.day, err));
+            }
+
+            let parsed = (solution.parse)(&input).unwrap();
+            assert_eq!(
+                (solution.run)(&input).unwrap(),
+                parsed.solve().unwrap(),
+                "{}",
+                solution.day
+            );
+        });
+    }
+}
@@ -0,0 +1,129 @@
+// Summary statistics for repeated in-process timing runs
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+
+        let runs = samples.len();
+        let total: Duration = samples.iter().sum();
+        // Exact unless there are more runs than a u32 can count
+        let mean = match u32::try_from(runs) {
+            Ok(runs) => total / runs,
+            Err(_) => total.div_f64(runs as f64),
+        };
+        Some(Self {
+            runs,
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+            mean,
+        })
+    }
+}
+
+// Nearest-rank percentile of sorted, non-empty samples
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (sorted.len() * pct).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+// Time func over n runs
+pub fn time<T>(n: usize, mut func: impl FnMut() -> T) -> Option<Stats> {
+    let samples = (0..n)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(func());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+// Time func over n runs, each on a fresh untimed setup
+pub fn time_batched<I, T>(
+    n: usize,
+    mut setup: impl FnMut() -> I,
+    mut func: impl FnMut(I) -> T,
+) -> Option<Stats> {
+    let samples = (0..n)
+        .map(|_| {
+            let input = setup();
+            let start = Instant::now();
+            let output = std::hint::black_box(func(input));
+            let elapsed = start.elapsed();
+            drop(output);
+            elapsed
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stats;
+    use std::time::Duration;
+    use test_case::test_case;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test_case(&[5] => (5, 5, 5, 5) ; "when single sample")]
+    #[test_case(&[4, 1, 3, 2] => (1, 2, 4, 2) ; "when unsorted")]
+    #[test_case(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 100]
+        => (1, 10, 19, 14) ; "when outlier")]
+    fn from_samples(samples: &[u64]) -> (u64, u64, u64, u64) {
+        let stats = Stats::from_samples(millis(samples)).unwrap();
+        assert_eq!(samples.len(), stats.runs);
+        (
+            stats.min.as_millis() as u64,
+            stats.median.as_millis() as u64,
+            stats.p95.as_millis() as u64,
+            stats.mean.as_millis() as u64,
+        )
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, Stats::from_samples(vec![]));
+        assert_eq!(None, super::time(0, || ()));
+    }
+
+    #[test]
+    fn time() {
+        let mut calls = 0;
+        let stats = super::time(10, || calls += 1).unwrap();
+        assert_eq!(10, calls);
+        assert_eq!(10, stats.runs);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+    }
+
+    #[test]
+    fn time_batched() {
+        let mut setups = 0;
+        let stats = super::time_batched(
+            10,
+            || {
+                setups += 1;
+                setups
+            },
+            |setup| setup * 2,
+        )
+        .unwrap();
+        assert_eq!(10, setups);
+        assert_eq!(10, stats.runs);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{range_map::RangeMap, AocError};
 use std::ops::Range;
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -44,39 +45,236 @@ impl Schematic {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-pub enum Day5 {
-    Seeds(Vec<i64>),
-    MapTitle(String, String),
-    Maps(Range<i64>, i64),
+// One of the almanac's maps, converting numbers of one category to the next
+#[derive(Default, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CategoryMap {
+    pub from: String,
+    pub to: String,
+    // Source range and the destination its start moves to
+    pub ranges: Vec<(Range<i64>, i64)>,
+}
+
+impl CategoryMap {
+    pub fn range_map(&self) -> RangeMap {
+        RangeMap::from_lines(&self.ranges)
+    }
+}
+
+// The day 5 puzzle input. The maps link categories into a graph, with at most one map
+// out of each category, so there's only ever one way from one category to another
+#[derive(Default, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<CategoryMap>,
 }
 
-impl Day5 {
-    pub fn seeds(self) -> Vec<i64> {
-        if let Self::Seeds(vec) = self {
-            vec
+impl Almanac {
+    pub fn map_from(&self, from: &str) -> Option<&CategoryMap> {
+        self.maps.iter().find(|map| map.from == from)
+    }
+
+    pub fn has_category(&self, category: &str) -> bool {
+        self.maps
+            .iter()
+            .any(|map| map.from == category || map.to == category)
+    }
+
+    // The maps leading from one category to another, in the order they apply
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap>, AocError> {
+        if let Some(category) = [from, to].into_iter().find(|cat| !self.has_category(cat)) {
+            return Err(AocError::input(
+                "almanac",
+                format!("there is no {} category", category),
+            ));
+        }
+
+        let mut path = vec![];
+        let mut category = from;
+        // Each map can only be used once on the way, more means going round in circles
+        while category != to && path.len() < self.maps.len() {
+            let Some(map) = self.map_from(category) else {
+                break;
+            };
+            path.push(map);
+            category = &map.to;
+        }
+
+        if category == to {
+            Ok(path)
         } else {
-            panic!("Called Day5::seeds on wrong enum variant");
+            Err(AocError::input(
+                "almanac",
+                format!("there is no way from {} to {}", from, to),
+            ))
         }
     }
 
-    pub fn titles(self) -> (String, String) {
-        if let Self::MapTitle(from, to) = self {
-            (from, to)
-        } else {
-            panic!("Called Day5::titles on wrong enum variant");
+    // The value at every category on the way from one category to another,
+    // starting with value itself
+    pub fn trace(&self, from: &str, to: &str, value: i64) -> Result<Vec<(String, i64)>, AocError> {
+        let mut trace = vec![(from.to_owned(), value)];
+        self.path(from, to)?.into_iter().fold(value, |value, map| {
+            let value = map.range_map().get(value);
+            trace.push((map.to.clone(), value));
+            value
+        });
+        Ok(trace)
+    }
+
+    // Every value in one category that ends up in the intervals in a later one
+    pub fn origins(
+        &self,
+        from: &str,
+        to: &str,
+        intervals: &[Range<i64>],
+    ) -> Result<Vec<Range<i64>>, AocError> {
+        Ok(self.map_between(from, to)?.preimage(intervals))
+    }
+
+    // Every map on the path from one category to another as one map
+    pub fn map_between(&self, from: &str, to: &str) -> Result<RangeMap, AocError> {
+        Ok(Self::compose(&self.path(from, to)?))
+    }
+
+    // The maps of a path, in order, as one map
+    pub fn compose(path: &[&CategoryMap]) -> RangeMap {
+        path.iter().fold(RangeMap::identity(), |map, next| {
+            map.compose(&next.range_map())
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum HandKind {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandKind {
+    // Jokers are counted towards whichever card is already most common
+    pub fn from_cards(cards: &[u8; 5]) -> Self {
+        let mut counts = [0u8; 15];
+        cards.iter().for_each(|&card| counts[card as usize] += 1);
+
+        let jokers = counts[Hand::JOKER as usize];
+        counts[Hand::JOKER as usize] = 0;
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match (counts[0] + jokers, counts[1]) {
+            (5, _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
         }
     }
+}
 
-    pub fn maps(self) -> Option<(Range<i64>, i64)> {
-        if let Self::Maps(src, dest) = self {
-            Some((src, dest))
-        } else {
-            None
+// Field order matters: hands compare by kind first, then card by card
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Hand {
+    pub kind: HandKind,
+    pub cards: [u8; 5],
+}
+
+impl Hand {
+    pub const JOKER: u8 = 1;
+    pub const JACK: u8 = 11;
+
+    pub fn new(cards: [u8; 5]) -> Self {
+        Self {
+            kind: HandKind::from_cards(&cards),
+            cards,
+        }
+    }
+
+    // Reinterpret every jack as a joker, which is wild but the weakest card
+    pub fn with_jokers(self) -> Self {
+        Self::new(self.cards.map(|card| {
+            if card == Self::JACK {
+                Self::JOKER
+            } else {
+                card
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Almanac, CategoryMap};
+    use std::ops::Range;
+    use test_case::test_case;
+
+    fn almanac() -> Almanac {
+        let map = |from: &str, to: &str, ranges| CategoryMap {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            ranges,
+        };
+        Almanac {
+            seeds: vec![79],
+            maps: vec![
+                map("soil", "water", vec![(0..10, 100)]),
+                map("seed", "soil", vec![(79..80, 5)]),
+                map("water", "light", vec![(100..110, 0)]),
+                map("loop", "around", vec![]),
+                map("around", "loop", vec![]),
+            ],
         }
     }
 
-    pub fn is_maps(&self) -> bool {
-        matches!(self, Self::Maps(_, _))
+    #[test_case("seed", "light" => vec!["seed", "soil", "water"] ; "when out of order")]
+    #[test_case("soil", "water" => vec!["soil"] ; "when one map")]
+    #[test_case("water", "water" => Vec::<String>::new() ; "when same category")]
+    fn path(from: &str, to: &str) -> Vec<String> {
+        almanac()
+            .path(from, to)
+            .unwrap()
+            .into_iter()
+            .map(|map| map.from.clone())
+            .collect()
+    }
+
+    #[test_case("seed", "humidity" => "almanac: there is no humidity category" ; "when unknown")]
+    #[test_case("light", "seed" => "almanac: there is no way from light to seed" ; "when backwards")]
+    #[test_case("loop", "seed" => "almanac: there is no way from loop to seed" ; "when cycle")]
+    fn path_errors(from: &str, to: &str) -> String {
+        almanac().path(from, to).unwrap_err().to_string()
+    }
+
+    #[test_case("seed", "light", 79 => 5 ; "when all moved")]
+    #[test_case("seed", "water", 79 => 105 ; "when part way")]
+    #[test_case("soil", "light", 79 => 79 ; "when not moved")]
+    fn map_between(from: &str, to: &str, num: i64) -> i64 {
+        almanac().map_between(from, to).unwrap().get(num)
+    }
+
+    #[test]
+    fn trace() {
+        let trace: Vec<_> = almanac().trace("seed", "light", 79).unwrap();
+        let expected = [("seed", 79), ("soil", 5), ("water", 105), ("light", 5)];
+        assert_eq!(
+            expected
+                .map(|(category, value)| (category.to_owned(), value))
+                .to_vec(),
+            trace
+        );
+    }
+
+    #[test_case(5 => vec![5..6, 79..80, 105..106] ; "when three ways")]
+    #[test_case(105 => Vec::<Range<i64>>::new() ; "when none")]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn origins(value: i64) -> Vec<Range<i64>> {
+        almanac()
+            .origins("seed", "light", &[value..value + 1])
+            .unwrap()
     }
 }
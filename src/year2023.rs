@@ -0,0 +1,520 @@
+// Advent of Code 2023
+use crate::{
+    parser,
+    types::{self, Schematic},
+    AocError,
+};
+use advent2023_macros::aoc_day;
+use rayon::prelude::*;
+
+#[cfg(test)]
+mod reference;
+
+aoc_day! {
+    1;
+    EXAMPLE: 142;
+    { input.sum() }
+}
+
+aoc_day! {
+    1p2;
+    EXAMPLE: ["day1p2" => 281, "day1p2_overlap" => 179];
+    PARSER: 1p2;
+    { input.sum() }
+}
+
+// A game that can't be read can't be shown to be possible, so it is left out
+aoc_day! {
+    2;
+    EXAMPLE: 8;
+    SKIP_INVALID;
+    {
+        const NUM_RED: i64 = 12;
+        const NUM_GREEN: i64 = 13;
+        const NUM_BLUE: i64 = 14;
+
+        input
+            .filter(|game| {
+                game.sets
+                    .par_iter()
+                    .find_any(|set| set.red > NUM_RED || set.green > NUM_GREEN || set.blue > NUM_BLUE)
+                    .is_none()
+            }).map(|game| game.id).sum()
+    }
+}
+
+mod day2p2 {
+    use crate::types::{Game, GameSet};
+    use rayon::prelude::*;
+
+    pub(super) fn get_power(game: Game) -> i64 {
+        let maximums = game.sets.into_par_iter().reduce(
+            || GameSet {
+                red: 0,
+                green: 0,
+                blue: 0,
+            },
+            |mut acc, set| {
+                acc.red = acc.red.max(set.red);
+                acc.green = acc.green.max(set.green);
+                acc.blue = acc.blue.max(set.blue);
+                acc
+            },
+        );
+        maximums.red * maximums.green * maximums.blue
+    }
+}
+
+aoc_day! {
+    2p2;
+    EXAMPLE: 2286;
+    {
+        input.map(day2p2::get_power).sum()
+    }
+}
+
+mod day3 {
+    use crate::types::Schematic;
+    use std::collections::{HashMap, HashSet};
+
+    // Wraps on the top and left edges, so those neighbours are never in the map
+    fn generate_adjacency(x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (left, up) = (x.wrapping_sub(1), y.wrapping_sub(1));
+        [
+            (x + 1, y),
+            (left, y),
+            (x, y + 1),
+            (x, up),
+            (x + 1, y + 1),
+            (x + 1, up),
+            (left, y + 1),
+            (left, up),
+        ]
+        .into_iter()
+    }
+
+    pub(super) fn linear_to_rect(idx: usize, width: usize) -> (usize, usize) {
+        (idx % width, idx / width)
+    }
+
+    pub(super) fn get_coordinate_map(
+        items: &[Schematic],
+        width: usize,
+    ) -> HashMap<(usize, usize), Schematic> {
+        items
+            .iter()
+            .flat_map(|schem| {
+                let span = schem.span();
+                (span.0..span.1).map(|idx| (linear_to_rect(idx, width), *schem))
+            })
+            .collect()
+    }
+
+    pub(super) fn get_adjacent_numbers_fn(
+        width: usize,
+        map: HashMap<(usize, usize), Schematic>,
+    ) -> impl FnMut(usize) -> i64 {
+        let mut already_added = HashSet::new();
+        move |idx: usize| {
+            let (x, y) = linear_to_rect(idx, width);
+            generate_adjacency(x, y)
+                .map(|coords| {
+                    if let Some(&schem) = map.get(&coords) {
+                        match schem {
+                            Schematic::Number(num, _) if already_added.insert(schem) => num,
+                            _ => 0,
+                        }
+                    } else {
+                        0
+                    }
+                })
+                .sum()
+        }
+    }
+
+    pub(super) fn get_gear_ratio(
+        idx: usize,
+        width: usize,
+        map: &HashMap<(usize, usize), Schematic>,
+    ) -> i64 {
+        let (x, y) = linear_to_rect(idx, width);
+        let mut already_found = vec![];
+
+        let adjacent_numbers: Vec<_> = generate_adjacency(x, y)
+            .filter_map(|coords| {
+                if let Some(&schem) = map.get(&coords) {
+                    match schem {
+                        Schematic::Number(num, span) => {
+                            if !already_found.contains(&span) {
+                                already_found.push(span);
+                                Some(num)
+                            } else {
+                                None
+                            }
+                        }
+                        _ => None,
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if adjacent_numbers.len() != 2 {
+            0
+        } else {
+            adjacent_numbers[0] * adjacent_numbers[1]
+        }
+    }
+}
+
+aoc_day! {
+    3;
+    EXAMPLE: 4361;
+    INPUT: WHOLE;
+    {
+        let ((width, _), items) = input;
+        let coordinate_map = day3::get_coordinate_map(&items, width);
+        let mut get_adjacent_numbers = day3::get_adjacent_numbers_fn(width, coordinate_map);
+        items
+            .iter()
+            .filter(|schem| schem.is_symbol())
+            .map(|symb| get_adjacent_numbers(symb.span().0))
+            .sum()
+    }
+}
+
+aoc_day! {
+    3p2;
+    EXAMPLE: 467835;
+    INPUT: WHOLE;
+    {
+        let ((width, _), items) = input;
+        let coordinate_map = day3::get_coordinate_map(&items, width);
+
+        items
+            .iter()
+            .filter(|schem| matches!(schem, Schematic::Symbol('*', _)))
+            .map(|symb| day3::get_gear_ratio(symb.span().0, width, &coordinate_map))
+            .sum()
+    }
+}
+
+fn get_winners((_id, winners, cards): &(u32, Vec<i64>, Vec<i64>)) -> usize {
+    cards.iter().filter(|num| winners.contains(num)).count()
+}
+
+aoc_day! {
+    4;
+    EXAMPLE: 13;
+    {
+        input
+            .map(|x| get_winners(&x))
+            .map(|num_winners| {
+                if num_winners == 0 {
+                    0
+                } else {
+                    1 << (num_winners - 1)
+                }
+            })
+            .sum()
+    }
+}
+
+mod day4p2;
+
+aoc_day! {
+    4p2;
+    EXAMPLE: 30;
+    {
+        let cards: Vec<_> = input.collect();
+
+        day4p2::Day4p2::new(&cards).map(|(num, _)| num as i64).sum()
+    }
+}
+
+fn no_seeds() -> AocError {
+    AocError::input("almanac", "there are no seeds to plant")
+}
+
+aoc_day! {
+    5;
+    EXAMPLE: 35;
+    INPUT: WHOLE;
+    OUTPUT: Result<i64, AocError>;
+    {
+        let map = input.map_between("seed", "location")?;
+        input
+            .seeds
+            .into_par_iter()
+            .map(|seed| map.get(seed))
+            .min()
+            .ok_or_else(no_seeds)
+    }
+}
+
+mod day5p2 {
+    use super::no_seeds;
+    use crate::{parser, range_map, types::Almanac, AocError};
+    use std::{ops::Range, slice};
+
+    // The seed numbers are pairs of range start and length
+    fn seed_ranges(almanac: &Almanac) -> Result<Vec<Range<i64>>, AocError> {
+        if !almanac.seeds.len().is_multiple_of(2) {
+            return Err(AocError::input(
+                "almanac",
+                format!(
+                    "{} seed numbers can't be paired into starts and lengths",
+                    almanac.seeds.len()
+                ),
+            ));
+        }
+        Ok(almanac
+            .seeds
+            .iter()
+            .array_chunks::<2usize>()
+            .map(|[start, len]| *start..start + len)
+            .collect())
+    }
+
+    pub(super) fn lowest_location(almanac: &Almanac) -> Result<i64, AocError> {
+        let seeds = seed_ranges(almanac)?;
+        // Sorted, so the first location is the lowest
+        almanac
+            .map_between("seed", "location")?
+            .apply(&seeds)
+            .first()
+            .map(|range| range.start)
+            .ok_or_else(no_seeds)
+    }
+
+    // Which seeds end up at the lowest location, and the way the lowest of them gets there
+    pub(super) fn explain(input: &[&str]) -> Result<String, AocError> {
+        let almanac = parser::day5::parse_input(input)?;
+        let seeds = seed_ranges(&almanac)?;
+        let location = lowest_location(&almanac)?;
+
+        let lowest = location..location + 1;
+        let origins = almanac.origins("seed", "location", slice::from_ref(&lowest))?;
+        // Only the seeds that were planted
+        let planted = range_map::normalize(origins.iter().flat_map(|origin| {
+            seeds
+                .iter()
+                .map(|seeds| origin.start.max(seeds.start)..origin.end.min(seeds.end))
+        }));
+        let seed = planted.first().ok_or_else(no_seeds)?.start;
+        let range = seeds
+            .iter()
+            .find(|range| range.contains(&seed))
+            .ok_or_else(no_seeds)?;
+
+        let trace = almanac
+            .trace("seed", "location", seed)?
+            .into_iter()
+            .map(|(category, value)| format!("{} {}", category, value))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        let planted = planted
+            .iter()
+            .map(|range| format!("{:?}", range))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(format!(
+            "lowest location {} comes from seed {} in the range {:?}\n\
+             seeds reaching it: {}\n\
+             {}",
+            location, seed, range, planted, trace
+        ))
+    }
+}
+
+aoc_day! {
+    5p2;
+    EXAMPLE: 46;
+    INPUT: WHOLE;
+    OUTPUT: Result<i64, AocError>;
+    EXPLAIN: day5p2::explain;
+    { day5p2::lowest_location(&input) }
+}
+
+mod day6;
+
+aoc_day! {
+    6;
+    EXAMPLE: 288;
+    INPUT: WHOLE;
+    {
+        let (times, records) = input;
+        times
+            .into_iter()
+            .zip(records)
+            .map(|(time, record)| day6::ways_to_win(time, record))
+            .product()
+    }
+}
+
+aoc_day! {
+    6p2;
+    EXAMPLE: 71503;
+    INPUT: WHOLE;
+    {
+        let (times, records) = input;
+        let fold_fn = |acc: i64, x: i64| {
+            if acc == 0 {
+                x
+            } else {
+                acc * i64::pow(10, x.ilog10() + 1) + x
+            }
+        };
+        let time = times.into_iter().fold(0, fold_fn);
+        let records = records.into_iter().fold(0, fold_fn);
+
+        day6::ways_to_win(time, records)
+    }
+}
+
+fn total_winnings(mut hands: Vec<(types::Hand, i64)>) -> i64 {
+    hands.par_sort_unstable();
+    hands
+        .into_iter()
+        .zip(1..)
+        .map(|((_, bid), rank)| bid * rank)
+        .sum()
+}
+
+aoc_day! {
+    7;
+    EXAMPLE: 6440;
+    {
+        total_winnings(input.collect())
+    }
+}
+
+aoc_day! {
+    7p2;
+    EXAMPLE: 5905;
+    {
+        total_winnings(
+            input
+                .map(|(hand, bid)| (hand.with_jokers(), bid))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use advent2023_macros::make_tests;
+
+    make_tests! {
+        YEAR: 2023;
+        REAL_INPUT_PATH: "bench_inputs";
+        REFERENCES: {
+            1p2 => crate::year2023::reference::day1p2;
+            4p2 => crate::year2023::reference::day4p2;
+            5 => crate::year2023::reference::day5;
+            5p2 => crate::year2023::reference::day5p2;
+            6 => crate::year2023::reference::day6;
+            6p2 => crate::year2023::reference::day6p2;
+            7 => crate::year2023::reference::day7;
+            7p2 => crate::year2023::reference::day7p2;
+        };
+    }
+
+    use crate::{inputs, Answer, AocError, Day, ParseError};
+    use nom::error::ErrorKind;
+    use test_case::test_case;
+
+    #[test]
+    fn try_day5p2_without_seeds() {
+        let err =
+            super::try_day5p2(&["seeds: 1 0", "", "seed-to-soil map:", "50 98 2"]).unwrap_err();
+        assert!(matches!(err, AocError::Input { .. }));
+    }
+
+    #[test]
+    fn try_day5p2_unpaired_seed() {
+        let err = super::try_day5p2(&["seeds: 79 14 55", "", "seed-to-soil map:", "50 98 2"])
+            .unwrap_err();
+        assert_eq!(
+            AocError::input(
+                "almanac",
+                "3 seed numbers can't be paired into starts and lengths"
+            ),
+            err
+        );
+    }
+
+    #[test]
+    fn explain_day5p2() {
+        let path = inputs::example_path(inputs::EXAMPLE_DIR, Day::new(5, true));
+        let lines = inputs::read_lines(path).unwrap();
+        let input: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let explanation = super::day5p2::explain(&input).unwrap();
+        assert!(
+            explanation.starts_with("lowest location 46 comes from seed 82 in the range 79..93")
+        );
+        assert!(explanation.ends_with("seed 82 -> soil 84 -> fertilizer 84 -> water 84 -> light 77 -> temperature 45 -> humidity 46 -> location 46"));
+    }
+
+    #[test]
+    fn try_day5_bad_line() {
+        let err = super::try_day5(&["seeds: 1 2", "", "seed-to-soil map:", "50 98"]).unwrap_err();
+        assert!(matches!(err, AocError::Parse(err) if (err.line, err.column) == (4, 6)));
+    }
+
+    #[test_case(crate::year2023::try_day2p2, &["Game 1: 3 blue", "Game 2: 4 rad"] => (2, 11) ; "day2p2")]
+    #[test_case(crate::year2023::try_day4, &["Card 1: 41 | 83", "Card 2: 1 2 x"] => (2, 13) ; "day4")]
+    #[test_case(crate::year2023::try_day7, &["32T3K 765", "", "KK677 28"] => (2, 1) ; "when blank")]
+    #[test_case(crate::year2023::try_day7p2, &["32T3K 765 1", "KK677 28 x"] => (1, 10) ; "when first bad line")]
+    fn bad_line(run: fn(&[&str]) -> Result<Answer, AocError>, input: &[&str]) -> (usize, usize) {
+        match run(input) {
+            Err(AocError::Parse(err)) => (err.line, err.column),
+            res => panic!("{:?}", res),
+        }
+    }
+
+    #[test]
+    fn skip_invalid_day2() {
+        let input = ["Game 1: 3 blue", "Game 2: 4 rad", "Game 3: 2 red"];
+        assert_eq!(Ok(Answer::from(4)), super::try_day2(&input));
+        let err = super::check_day2(&input).unwrap_err();
+        assert!(matches!(err, AocError::Parse(err) if (err.line, err.column) == (2, 11)));
+    }
+
+    #[test]
+    fn try_day6_trailing() {
+        let err = super::try_day6(&["Time: 7", "Distance: 9 x"]).unwrap_err();
+        assert!(matches!(err, AocError::Parse(err) if (err.line, err.column) == (2, 12)));
+    }
+
+    #[test_case(crate::year2023::try_day6 ; "day6")]
+    #[test_case(crate::year2023::try_day6p2 ; "day6p2")]
+    fn try_day6_unequal(run: fn(&[&str]) -> Result<Answer, AocError>) {
+        let err = run(&["Time: 7 15", "Distance: 9"]).unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Input {
+                context: "races",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn try_day6_bad_line() {
+        let err = super::try_day6(&["Time: 7", "Distance: x"]).unwrap_err();
+        assert_eq!(
+            AocError::Parse(ParseError {
+                line: 2,
+                column: 11,
+                context: "Distance:",
+                kind: ErrorKind::Digit,
+                expected: None,
+                stack: vec![],
+                source: String::from("Distance: x"),
+            }),
+            err
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// Slow but obviously correct solutions, checked against the real ones on small
+// generated inputs by make_tests!. They parse for themselves and panic on bad input
+use std::collections::HashSet;
+
+const DIGIT_NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn numbers(text: &str) -> Vec<i64> {
+    text.split_whitespace()
+        .map(|num| num.parse().unwrap())
+        .collect()
+}
+
+// Try every position for a digit or a spelled out digit
+pub fn day1p2(input: &[&str]) -> i64 {
+    input
+        .iter()
+        .map(|line| {
+            let digits: Vec<i64> = (0..line.len())
+                .filter_map(|idx| {
+                    let rest = &line[idx..];
+                    let literal = rest.chars().next().and_then(|ch| ch.to_digit(10));
+                    let spelled = DIGIT_NAMES
+                        .iter()
+                        .position(|name| rest.starts_with(name))
+                        .map(|pos| pos as u32 + 1);
+                    literal.or(spelled).map(i64::from)
+                })
+                .collect();
+            digits.first().unwrap() * 10 + digits.last().unwrap()
+        })
+        .sum()
+}
+
+// Hand out every copy one at a time
+pub fn day4p2(input: &[&str]) -> i64 {
+    let matches: Vec<usize> = input
+        .iter()
+        .map(|line| {
+            let (winners, card) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+            let winners: HashSet<i64> = numbers(winners).into_iter().collect();
+            numbers(card)
+                .into_iter()
+                .filter(|num| winners.contains(num))
+                .count()
+        })
+        .collect();
+
+    let mut pending: Vec<usize> = (0..matches.len()).collect();
+    let mut total = 0;
+    while let Some(card) = pending.pop() {
+        total += 1;
+        pending.extend(card + 1..=card + matches[card]);
+    }
+    total
+}
+
+// Each line of a map as (destination, source, length)
+type Map = Vec<(i64, i64, i64)>;
+
+// Seeds, then each map
+fn almanac(input: &[&str]) -> (Vec<i64>, Vec<Map>) {
+    let seeds = numbers(input[0].strip_prefix("seeds:").unwrap());
+    let mut maps: Vec<Vec<_>> = vec![];
+    input[1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .for_each(|line| {
+            if line.ends_with("map:") {
+                maps.push(vec![]);
+            } else if let [dest, src, len] = numbers(line)[..] {
+                maps.last_mut().unwrap().push((dest, src, len));
+            }
+        });
+    (seeds, maps)
+}
+
+fn location(seed: i64, maps: &[Map]) -> i64 {
+    maps.iter().fold(seed, |num, map| {
+        map.iter()
+            .find(|(_, src, len)| (*src..src + len).contains(&num))
+            .map_or(num, |(dest, src, _)| dest + num - src)
+    })
+}
+
+// Follow each seed through every map
+pub fn day5(input: &[&str]) -> i64 {
+    let (seeds, maps) = almanac(input);
+    seeds
+        .into_iter()
+        .map(|seed| location(seed, &maps))
+        .min()
+        .unwrap()
+}
+
+// Follow every seed in every range
+pub fn day5p2(input: &[&str]) -> i64 {
+    let (seeds, maps) = almanac(input);
+    seeds
+        .chunks(2)
+        .flat_map(|range| range[0]..range[0] + range[1])
+        .map(|seed| location(seed, &maps))
+        .min()
+        .unwrap()
+}
+
+fn ways_to_win(time: i64, record: i64) -> i64 {
+    (0..=time)
+        .filter(|held| (time - held) * held > record)
+        .count() as i64
+}
+
+fn row(line: &str) -> &str {
+    line.split_once(':').unwrap().1
+}
+
+// Race with every hold time
+pub fn day6(input: &[&str]) -> i64 {
+    numbers(row(input[0]))
+        .into_iter()
+        .zip(numbers(row(input[1])))
+        .map(|(time, record)| ways_to_win(time, record))
+        .product()
+}
+
+pub fn day6p2(input: &[&str]) -> i64 {
+    let joined = |line| row(line).replace(' ', "").parse().unwrap();
+    ways_to_win(joined(input[0]), joined(input[1]))
+}
+
+const CARDS: &str = "23456789TJQKA";
+const JOKER_CARDS: &str = "J23456789TQKA";
+
+// Higher is stronger, from the sorted counts of each card
+fn hand_type(hand: &str) -> Vec<usize> {
+    let mut counts: Vec<usize> = CARDS
+        .chars()
+        .map(|card| hand.chars().filter(|ch| *ch == card).count())
+        .filter(|count| *count > 0)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts
+}
+
+// Rank every hand by comparing it against all the others
+// Equal hands are ordered by bid, then by position
+fn winnings(input: &[&str], key: impl Fn(&str) -> (Vec<usize>, Vec<usize>)) -> i64 {
+    let hands: Vec<(_, i64, usize)> = input
+        .iter()
+        .zip(0..)
+        .map(|(line, idx)| {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            (key(hand), bid.parse().unwrap(), idx)
+        })
+        .collect();
+
+    hands
+        .iter()
+        .map(|hand| {
+            let rank = 1 + hands.iter().filter(|other| *other < hand).count() as i64;
+            rank * hand.1
+        })
+        .sum()
+}
+
+pub fn day7(input: &[&str]) -> i64 {
+    winnings(input, |hand| {
+        let order = hand.chars().map(|ch| CARDS.find(ch).unwrap()).collect();
+        (hand_type(hand), order)
+    })
+}
+
+// Try every card in place of the jokers
+pub fn day7p2(input: &[&str]) -> i64 {
+    winnings(input, |hand| {
+        let best = CARDS
+            .chars()
+            .map(|card| hand_type(&hand.replace('J', &card.to_string())))
+            .max()
+            .unwrap();
+        let order = hand
+            .chars()
+            .map(|ch| JOKER_CARDS.find(ch).unwrap())
+            .collect();
+        (best, order)
+    })
+}